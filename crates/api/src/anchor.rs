@@ -0,0 +1,144 @@
+use crate::opts::{GetExtmarkByIdOpts, SetExtmarkOpts};
+use crate::types::{ByteCol, ExtmarkInfos, Gravity, Row0};
+use crate::{Buffer, Error, Result};
+
+/// The name of the namespace the extmarks backing the anchors are created in.
+const NAMESPACE: &str = "nvim-oxi-anchors";
+
+/// A position in a buffer that follows the edits made to its text, backed
+/// by an invisible extmark.
+///
+/// The extmark is removed when the anchor is dropped.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::api::{Anchor, Buffer};
+/// use nvim_oxi::api::types::{ByteCol, Gravity, Row0};
+///
+/// let mut buffer = Buffer::current();
+/// let anchor = Anchor::new(&buffer, (Row0(2), ByteCol(0)), Gravity::Right)?;
+///
+/// buffer.set_lines(Row0(0)..Row0(0), true, ["new line"])?;
+///
+/// assert_eq!(anchor.position()?, (Row0(3), ByteCol(0)));
+/// ```
+#[derive(Debug)]
+pub struct Anchor {
+    extmark: AnchorExtmark,
+}
+
+/// A range of a buffer whose ends follow the edits made to its text, backed
+/// by an invisible extmark.
+///
+/// The extmark is removed when the anchor is dropped.
+#[derive(Debug)]
+pub struct RangeAnchor {
+    extmark: AnchorExtmark,
+}
+
+/// The extmark shared by both kinds of anchors.
+#[derive(Debug)]
+struct AnchorExtmark {
+    buffer: Buffer,
+    ns_id: u32,
+    id: u32,
+}
+
+impl Anchor {
+    /// Creates a new anchor at the given position.
+    pub fn new(
+        buffer: &Buffer,
+        (row, col): (Row0, ByteCol),
+        gravity: Gravity,
+    ) -> Result<Self> {
+        let opts = SetExtmarkOpts::builder()
+            .right_gravity(gravity == Gravity::Right)
+            .build();
+
+        AnchorExtmark::new(buffer, row, col, &opts)
+            .map(|extmark| Self { extmark })
+    }
+
+    /// Returns the buffer the anchor is in.
+    #[inline]
+    pub fn buffer(&self) -> &Buffer {
+        &self.extmark.buffer
+    }
+
+    /// Returns the current position of the anchor.
+    pub fn position(&self) -> Result<(Row0, ByteCol)> {
+        self.extmark.get(false).map(|(row, col, _)| (row, col))
+    }
+}
+
+impl RangeAnchor {
+    /// Creates a new anchor spanning the given range. The gravities of the
+    /// two ends decide whether the range grows when text is inserted at its
+    /// edges, e.g. a `(Left, Right)` range always includes it.
+    pub fn new(
+        buffer: &Buffer,
+        (start_row, start_col): (Row0, ByteCol),
+        (end_row, end_col): (Row0, ByteCol),
+        (start_gravity, end_gravity): (Gravity, Gravity),
+    ) -> Result<Self> {
+        let opts = SetExtmarkOpts::builder()
+            .end_row(end_row)
+            .end_col(end_col)
+            .right_gravity(start_gravity == Gravity::Right)
+            .end_right_gravity(end_gravity == Gravity::Right)
+            .build();
+
+        AnchorExtmark::new(buffer, start_row, start_col, &opts)
+            .map(|extmark| Self { extmark })
+    }
+
+    /// Returns the buffer the anchor is in.
+    #[inline]
+    pub fn buffer(&self) -> &Buffer {
+        &self.extmark.buffer
+    }
+
+    /// Returns the current start and end positions of the anchor. The end
+    /// position is exclusive.
+    pub fn range(&self) -> Result<((Row0, ByteCol), (Row0, ByteCol))> {
+        let (row, col, infos) = self.extmark.get(true)?;
+
+        let (end_row, end_col) = infos
+            .and_then(|infos| infos.end_row.zip(infos.end_col))
+            .ok_or_else(|| Error::custom("the anchor's extmark has no end"))?;
+
+        Ok(((row, col), (end_row, end_col)))
+    }
+}
+
+impl AnchorExtmark {
+    fn new(
+        buffer: &Buffer,
+        row: Row0,
+        col: ByteCol,
+        opts: &SetExtmarkOpts,
+    ) -> Result<Self> {
+        let ns_id = crate::create_namespace(NAMESPACE);
+        let mut buffer = buffer.clone();
+        let id = buffer.set_extmark(ns_id, row, col, opts)?;
+        Ok(Self { buffer, ns_id, id })
+    }
+
+    fn get(
+        &self,
+        details: bool,
+    ) -> Result<(Row0, ByteCol, Option<ExtmarkInfos>)> {
+        let opts = GetExtmarkByIdOpts::builder().details(details).build();
+        self.buffer.get_extmark_by_id(self.ns_id, self.id, &opts)
+    }
+}
+
+impl Drop for AnchorExtmark {
+    fn drop(&mut self) {
+        // The extmark is already gone if the buffer was deleted.
+        if self.buffer.is_valid() {
+            let _ = self.buffer.del_extmark(self.ns_id, self.id);
+        }
+    }
+}
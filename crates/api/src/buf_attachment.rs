@@ -0,0 +1,58 @@
+use core::cell::Cell;
+use std::rc::Rc;
+
+use crate::Buffer;
+
+/// A handle to the callbacks attached to a buffer with
+/// [`Buffer::attach`].
+///
+/// The callbacks are detached when the handle is dropped. Since Neovim
+/// doesn't provide a way to detach them directly, that happens the next time
+/// one of them is triggered, at which point it returns `true` without being
+/// called. Use [`keep`](BufAttachment::keep) to leave them attached for as
+/// long as the buffer exists.
+#[must_use = "the callbacks are detached when the handle is dropped"]
+#[derive(Debug)]
+pub struct BufAttachment {
+    buffer: Buffer,
+    detached: Rc<Cell<bool>>,
+}
+
+impl BufAttachment {
+    #[inline]
+    pub(crate) fn new(buffer: Buffer, detached: Rc<Cell<bool>>) -> Self {
+        Self { buffer, detached }
+    }
+
+    /// Returns the buffer the callbacks are attached to.
+    #[inline]
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Detaches the callbacks from the buffer. This is the same as dropping
+    /// the handle.
+    #[inline]
+    pub fn detach(self) {}
+
+    /// Whether the callbacks are still attached, i.e. whether they haven't
+    /// been detached by Neovim, for example because the buffer was unloaded
+    /// or because one of them returned `true`.
+    #[inline]
+    pub fn is_attached(&self) -> bool {
+        !self.detached.get()
+    }
+
+    /// Consumes the handle without detaching the callbacks.
+    #[inline]
+    pub fn keep(self) {
+        core::mem::forget(self);
+    }
+}
+
+impl Drop for BufAttachment {
+    #[inline]
+    fn drop(&mut self) {
+        self.detached.set(true);
+    }
+}
@@ -1,7 +1,9 @@
+use core::cell::Cell;
 use core::ops::RangeBounds;
 use std::error::Error as StdError;
 use std::fmt;
 use std::path::Path;
+use std::rc::Rc;
 use std::result::Result as StdResult;
 
 use luajit::{self as lua, Poppable, Pushable};
@@ -16,12 +18,21 @@ use types::{
     conversion::{self, FromObject, ToObject},
 };
 
+use crate::BufAttachment;
 use crate::LUA_INTERNAL_CALL;
 use crate::SuperIterator;
 use crate::choose;
 use crate::ffi::buffer::*;
 use crate::opts::*;
-use crate::types::{KeymapInfos, Mode};
+use crate::types::{
+    ByteCol,
+    KeymapInfos,
+    Mode,
+    Position,
+    PositionEncoding,
+    Row0,
+    Row1,
+};
 use crate::utils;
 use crate::{Error, IntoResult, Result};
 
@@ -116,22 +127,28 @@ impl Buffer {
 
     /// Binding to [`nvim_buf_attach()`][1].
     ///
-    /// Used to register a set of callbacks on specific buffer events.
+    /// Used to register a set of callbacks on specific buffer events. The
+    /// callbacks stay attached until the returned [`BufAttachment`] is
+    /// dropped, until one of them returns `true`, or until the buffer is
+    /// unloaded.
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_buf_attach()
     pub fn attach(
         &self,
         send_buffer: bool,
         opts: &BufAttachOpts,
-    ) -> Result<()> {
+    ) -> Result<BufAttachment> {
         let mut err = nvim::Error::new();
 
+        let detached = Rc::new(Cell::new(false));
+        let opts = opts.detachable(&detached);
+
         let has_attached = unsafe {
             nvim_buf_attach(
                 LUA_INTERNAL_CALL,
                 self.0,
                 send_buffer,
-                opts,
+                &opts,
                 &mut err,
             )
         };
@@ -139,7 +156,7 @@ impl Buffer {
         choose!(
             err,
             match has_attached {
-                true => Ok(()),
+                true => Ok(BufAttachment::new(self.clone(), detached)),
                 _ => Err(Error::custom("Attaching to buffer failed")),
             }
         )
@@ -191,6 +208,34 @@ impl Buffer {
         })
     }
 
+    /// Converts a position to the given encoding, reading its line from the
+    /// buffer. See [`vim.str_utfindex()`][1] and [`vim.str_byteindex()`][2]
+    /// for how columns in the middle of a character are handled.
+    ///
+    /// [1]: https://neovim.io/doc/user/lua.html#vim.str_utfindex()
+    /// [2]: https://neovim.io/doc/user/lua.html#vim.str_byteindex()
+    pub fn convert_position(
+        &self,
+        position: Position,
+        encoding: PositionEncoding,
+    ) -> Result<Position> {
+        if position.encoding == encoding {
+            return Ok(position);
+        }
+
+        let line = self
+            .get_lines(position.row..=position.row, true)?
+            .next()
+            .unwrap_or_default();
+
+        position.to_encoding(line.as_bytes(), encoding).ok_or_else(|| {
+            Error::custom(format!(
+                "column {} is out of bounds for line {}",
+                position.col, position.row.0
+            ))
+        })
+    }
+
     /// Binding to [`nvim_buf_del_keymap()`][1].
     ///
     /// Unmaps a buffer-local mapping for the given mode.
@@ -303,7 +348,7 @@ impl Buffer {
         strict_indexing: bool,
     ) -> Result<impl SuperIterator<nvim::String> + use<R>>
     where
-        R: RangeBounds<usize>,
+        R: RangeBounds<Row0>,
     {
         let mut err = nvim::Error::new();
         let (start, end) = utils::range_to_limits(line_range);
@@ -331,13 +376,27 @@ impl Buffer {
         )
     }
 
+    /// Like [`get_lines`](Buffer::get_lines), but returns the lines as raw
+    /// bytes, which don't have to be valid UTF-8.
+    pub fn get_lines_bytes<R>(
+        &self,
+        line_range: R,
+        strict_indexing: bool,
+    ) -> Result<impl SuperIterator<Vec<u8>> + use<R>>
+    where
+        R: RangeBounds<Row0>,
+    {
+        self.get_lines(line_range, strict_indexing)
+            .map(|lines| lines.map(|line| line.as_bytes().to_owned()))
+    }
+
     /// Binding to [`nvim_buf_get_mark()`][1].
     ///
     /// Returns a (1-0) indexed `(row, col)` tuple representing the position
-    /// of the named mark.
+    /// of the named mark, or `None` if the mark is not set.
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_buf_get_mark()
-    pub fn get_mark(&self, name: char) -> Result<(usize, usize)> {
+    pub fn get_mark(&self, name: char) -> Result<Option<(Row1, ByteCol)>> {
         let mut err = nvim::Error::new();
         let name = nvim::String::from(name);
         let mark = unsafe {
@@ -349,10 +408,12 @@ impl Buffer {
             )
         };
         choose!(err, {
-            let mut iter = mark.into_iter().map(usize::from_object);
-            let row = iter.next().expect("row is present")?;
-            let col = iter.next().expect("col is present")?;
-            Ok((row, col))
+            let mut iter = mark.into_iter();
+            let row =
+                usize::from_object(iter.next().expect("row is present"))?;
+            let col =
+                ByteCol::from_object(iter.next().expect("col is present"))?;
+            Ok(Row1::new(row).map(|row| (row, col)))
         })
     }
 
@@ -372,10 +433,11 @@ impl Buffer {
     /// Returns the 0-indexed byte offset of a line.
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_buf_get_offset()
-    pub fn get_offset(&self, index: usize) -> Result<usize> {
+    pub fn get_offset(&self, index: Row0) -> Result<usize> {
         let mut err = nvim::Error::new();
-        let offset =
-            unsafe { nvim_buf_get_offset(self.0, index as Integer, &mut err) };
+        let offset = unsafe {
+            nvim_buf_get_offset(self.0, index.0 as Integer, &mut err)
+        };
         choose!(err, Ok(offset.try_into().expect("offset is positive")))
     }
 
@@ -391,12 +453,12 @@ impl Buffer {
     pub fn get_text<R>(
         &self,
         line_range: R,
-        start_col: usize,
-        end_col: usize,
+        start_col: ByteCol,
+        end_col: ByteCol,
         opts: &GetTextOpts,
     ) -> Result<impl SuperIterator<nvim::String> + use<R>>
     where
-        R: RangeBounds<usize>,
+        R: RangeBounds<Row0>,
     {
         let mut err = nvim::Error::new();
         let (start, end) = utils::range_to_limits(line_range);
@@ -405,9 +467,9 @@ impl Buffer {
                 LUA_INTERNAL_CALL,
                 self.0,
                 start,
-                start_col.try_into()?,
+                start_col.0.try_into()?,
                 end,
-                end_col.try_into()?,
+                end_col.0.try_into()?,
                 opts,
                 types::arena(),
                 // The nvim_buf_get_text() function returns no line if we use
@@ -426,6 +488,22 @@ impl Buffer {
         )
     }
 
+    /// Like [`get_text`](Buffer::get_text), but returns the lines as raw
+    /// bytes, which don't have to be valid UTF-8.
+    pub fn get_text_bytes<R>(
+        &self,
+        line_range: R,
+        start_col: ByteCol,
+        end_col: ByteCol,
+        opts: &GetTextOpts,
+    ) -> Result<impl SuperIterator<Vec<u8>> + use<R>>
+    where
+        R: RangeBounds<Row0>,
+    {
+        self.get_text(line_range, start_col, end_col, opts)
+            .map(|lines| lines.map(|line| line.as_bytes().to_owned()))
+    }
+
     /// Binding to [`nvim_buf_get_var()`][1].
     ///
     /// Gets a buffer-scoped (`b:`) variable.
@@ -521,7 +599,7 @@ impl Buffer {
         replacement: Lines,
     ) -> Result<()>
     where
-        R: RangeBounds<usize>,
+        R: RangeBounds<Row0>,
         Lines: IntoIterator<Item = Line>,
         Line: Into<nvim::String>,
     {
@@ -543,17 +621,36 @@ impl Buffer {
         choose!(err, ())
     }
 
+    /// Like [`set_lines`](Buffer::set_lines), but takes the replacement
+    /// lines as raw bytes, which don't have to be valid UTF-8.
+    pub fn set_lines_bytes<Line, Lines, R>(
+        &mut self,
+        line_range: R,
+        strict_indexing: bool,
+        replacement: Lines,
+    ) -> Result<()>
+    where
+        R: RangeBounds<Row0>,
+        Lines: IntoIterator<Item = Line>,
+        Line: AsRef<[u8]>,
+    {
+        let replacement = replacement
+            .into_iter()
+            .map(|line| nvim::String::from_bytes(line.as_ref()));
+        self.set_lines(line_range, strict_indexing, replacement)
+    }
+
     /// Binding to [`nvim_buf_set_mark()`][1].
     ///
-    /// Sets a named mark in the buffer. Marks are (1,0)-indexed, and passing 0
-    /// as `line` deletes the mark.
+    /// Sets a named mark in the buffer. Marks are (1,0)-indexed. Use
+    /// [`del_mark`](Buffer::del_mark) to delete a mark.
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_buf_set_mark()
     pub fn set_mark(
         &mut self,
         name: char,
-        line: usize,
-        col: usize,
+        line: Row1,
+        col: ByteCol,
         opts: &SetMarkOpts,
     ) -> Result<()> {
         let mut err = nvim::Error::new();
@@ -562,8 +659,8 @@ impl Buffer {
             nvim_buf_set_mark(
                 self.0,
                 name.as_nvim_str(),
-                line.try_into()?,
-                col.try_into()?,
+                line.get().try_into()?,
+                col.0.try_into()?,
                 opts,
                 &mut err,
             )
@@ -598,12 +695,12 @@ impl Buffer {
     pub fn set_text<Line, Lines, R>(
         &mut self,
         line_range: R,
-        start_col: usize,
-        end_col: usize,
+        start_col: ByteCol,
+        end_col: ByteCol,
         replacement: Lines,
     ) -> Result<()>
     where
-        R: RangeBounds<usize>,
+        R: RangeBounds<Row0>,
         Lines: IntoIterator<Item = Line>,
         Line: Into<nvim::String>,
     {
@@ -614,9 +711,9 @@ impl Buffer {
                 LUA_INTERNAL_CALL,
                 self.0,
                 start,
-                start_col.try_into()?,
+                start_col.0.try_into()?,
                 end,
-                end_col.try_into()?,
+                end_col.0.try_into()?,
                 replacement
                     .into_iter()
                     .map(|line| line.into())
@@ -0,0 +1,154 @@
+use core::ops::{Bound, ControlFlow, RangeBounds};
+
+use types::{self as nvim, NvimStr};
+
+use crate::Buffer;
+use crate::types::Row0;
+use crate::{Error, Result};
+
+/// The number of lines read at once by [`Buffer::for_each_line`].
+const FOR_EACH_CHUNK_SIZE: usize = 1024;
+
+/// An iterator over chunks of lines of a [`Buffer`], created by
+/// [`Buffer::lines_chunked`].
+///
+/// Every chunk is read from the buffer when the iterator is advanced, so
+/// edits made while iterating are visible in the following chunks.
+#[derive(Clone, Debug)]
+pub struct LinesChunked {
+    buffer: Buffer,
+    next_line: usize,
+    end_line: usize,
+    chunk_size: usize,
+}
+
+impl Buffer {
+    /// Returns an iterator over the lines in the given 0-indexed,
+    /// end-exclusive range, in chunks of `chunk_size` lines.
+    ///
+    /// Unlike [`get_lines`](Buffer::get_lines), at most `chunk_size` lines
+    /// are held in memory at any time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn lines_chunked<R>(
+        &self,
+        line_range: R,
+        chunk_size: usize,
+    ) -> Result<LinesChunked>
+    where
+        R: RangeBounds<Row0>,
+    {
+        assert!(chunk_size > 0, "chunk size must be positive");
+        let (start, end) = self.resolve_range(line_range)?;
+        Ok(LinesChunked {
+            buffer: self.clone(),
+            next_line: start,
+            end_line: end,
+            chunk_size,
+        })
+    }
+
+    /// Calls `fun` with the 0-indexed row and the content of every line in
+    /// the given range.
+    ///
+    /// The iteration stops at the first line for which `fun` returns
+    /// [`ControlFlow::Break`], whose value is returned. Lines are read with
+    /// [`lines_chunked`](Buffer::lines_chunked), so the whole range is never
+    /// held in memory at once.
+    ///
+    /// Every line is still copied out of Neovim's memory, since its API has
+    /// no way to borrow the content of a buffer. The [`NvimStr`] given to
+    /// `fun` borrows from that copy, which is freed with the rest of its
+    /// chunk.
+    pub fn for_each_line<R, F, B>(
+        &self,
+        line_range: R,
+        mut fun: F,
+    ) -> Result<Option<B>>
+    where
+        R: RangeBounds<Row0>,
+        F: FnMut(Row0, NvimStr<'_>) -> ControlFlow<B>,
+    {
+        let chunks = self.lines_chunked(line_range, FOR_EACH_CHUNK_SIZE)?;
+        let mut row = chunks.next_line;
+
+        for chunk in chunks {
+            for line in chunk? {
+                if let ControlFlow::Break(value) =
+                    fun(Row0(row), line.as_nvim_str())
+                {
+                    return Ok(Some(value));
+                }
+                row += 1;
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Converts a line range to concrete `(start, end)` limits, checking
+    /// that it's inside the buffer.
+    fn resolve_range<R>(&self, line_range: R) -> Result<(usize, usize)>
+    where
+        R: RangeBounds<Row0>,
+    {
+        let line_count = self.line_count()?;
+
+        let start = match line_range.start_bound() {
+            Bound::Included(&Row0(n)) => n,
+            Bound::Excluded(&Row0(n)) => n + 1,
+            Bound::Unbounded => 0,
+        };
+
+        let end = match line_range.end_bound() {
+            Bound::Included(&Row0(n)) => n + 1,
+            Bound::Excluded(&Row0(n)) => n,
+            Bound::Unbounded => line_count,
+        };
+
+        if start > end || end > line_count {
+            return Err(Error::custom(format!(
+                "line range {start}..{end} is out of bounds for a buffer \
+                 with {line_count} lines"
+            )));
+        }
+
+        Ok((start, end))
+    }
+}
+
+impl Iterator for LinesChunked {
+    type Item = Result<Vec<nvim::String>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_line >= self.end_line {
+            return None;
+        }
+
+        let start = self.next_line;
+        let end = (start + self.chunk_size).min(self.end_line);
+        self.next_line = end;
+
+        let chunk = self.buffer.get_lines(Row0(start)..Row0(end), true);
+
+        // Stop after the first error instead of failing on every chunk.
+        if chunk.is_err() {
+            self.next_line = self.end_line;
+        }
+
+        Some(chunk.map(Iterator::collect))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end_line.saturating_sub(self.next_line);
+        let chunks = remaining.div_ceil(self.chunk_size);
+        (chunks, Some(chunks))
+    }
+}
+
+impl ExactSizeIterator for LinesChunked {}
+
+impl core::iter::FusedIterator for LinesChunked {}
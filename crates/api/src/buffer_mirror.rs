@@ -0,0 +1,386 @@
+use core::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::opts::{BufAttachOpts, GetTextOpts};
+use crate::types::{BufEvent, ByteCol, Row0};
+use crate::{BufAttachment, Buffer, Error, Result};
+
+/// A copy of a buffer's lines kept in sync with Neovim by applying every
+/// [`Bytes`](BufEvent::Bytes) event it receives.
+///
+/// The mirror is detached from the buffer when dropped. Lines are stored as
+/// raw bytes, since buffers are not guaranteed to contain valid UTF-8.
+///
+/// Undo and redo restore the buffer's text before reporting the individual
+/// edits, so the mirror reads the whole buffer again when they're done.
+///
+/// If the buffer is unloaded or wiped, or if a change can't be read back from
+/// it, the mirror keeps its last content and becomes
+/// [stale](BufferMirror::is_stale).
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::api::{Buffer, BufferMirror};
+///
+/// let mirror = BufferMirror::new(&Buffer::current())?;
+/// let snapshot = mirror.snapshot();
+///
+/// std::thread::spawn(move || {
+///     let text = snapshot.text();
+///     // Parse the text off the main thread.
+/// });
+/// ```
+pub struct BufferMirror {
+    buffer: Buffer,
+    state: Rc<RefCell<MirrorState>>,
+    _attachment: BufAttachment,
+}
+
+/// An immutable snapshot of a [`BufferMirror`], which can be sent to other
+/// threads.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BufferSnapshot {
+    changedtick: u32,
+    lines: Arc<[Arc<[u8]>]>,
+}
+
+struct MirrorState {
+    changedtick: u32,
+    lines: Vec<Arc<[u8]>>,
+    detached: bool,
+    error: Option<Error>,
+}
+
+impl BufferMirror {
+    /// Creates a new mirror of the given buffer.
+    pub fn new(buffer: &Buffer) -> Result<Self> {
+        let state = Rc::new(RefCell::new(MirrorState {
+            changedtick: buffer.get_changedtick()?,
+            lines: read_lines(buffer)?,
+            detached: false,
+            error: None,
+        }));
+
+        let opts = {
+            let state = Rc::clone(&state);
+            BufAttachOpts::builder()
+                .on_event(move |event| {
+                    state.borrow_mut().on_event(event);
+                    false
+                })
+                .build()
+        };
+
+        let attachment = buffer.attach(false, &opts)?;
+
+        Ok(Self { buffer: buffer.clone(), state, _attachment: attachment })
+    }
+
+    /// Returns the mirrored buffer.
+    #[inline]
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Checks that the mirror matches the buffer's current content, returning
+    /// an error describing the first mismatching line if it doesn't.
+    pub fn check_consistency(&self) -> Result<()> {
+        let state = self.state.borrow();
+
+        if let Some(err) = &state.error {
+            return Err(err.clone());
+        }
+
+        if state.detached {
+            return Err(Error::custom("mirror was detached from the buffer"));
+        }
+
+        let actual = read_lines(&self.buffer)?;
+
+        if let Some((idx, (mirrored, actual))) = state
+            .lines
+            .iter()
+            .zip(&actual)
+            .enumerate()
+            .find(|(_, (mirrored, actual))| mirrored != actual)
+        {
+            return Err(Error::custom(format!(
+                "line {idx} differs: mirror has {:?}, buffer has {:?}",
+                String::from_utf8_lossy(mirrored),
+                String::from_utf8_lossy(actual),
+            )));
+        }
+
+        if state.lines.len() != actual.len() {
+            return Err(Error::custom(format!(
+                "mirror has {} lines, buffer has {}",
+                state.lines.len(),
+                actual.len()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the value of the buffer's `b:changedtick` after the last
+    /// change applied to the mirror.
+    #[inline]
+    pub fn changedtick(&self) -> u32 {
+        self.state.borrow().changedtick
+    }
+
+    /// Returns the error raised while reading the last change from the
+    /// buffer, if any. The mirror resynchronizes with the buffer on the next
+    /// change it receives.
+    #[inline]
+    pub fn error(&self) -> Option<Error> {
+        self.state.borrow().error.clone()
+    }
+
+    /// Whether the mirror stopped tracking the buffer, either because it was
+    /// detached from it (e.g. because the buffer was unloaded or wiped) or
+    /// because its last change couldn't be read.
+    #[inline]
+    pub fn is_stale(&self) -> bool {
+        let state = self.state.borrow();
+        state.detached || state.error.is_some()
+    }
+
+    /// Returns the line at the given index, if it exists.
+    #[inline]
+    pub fn line(&self, idx: usize) -> Option<Arc<[u8]>> {
+        self.state.borrow().lines.get(idx).cloned()
+    }
+
+    /// Returns the number of lines in the buffer.
+    #[inline]
+    pub fn line_count(&self) -> usize {
+        self.state.borrow().lines.len()
+    }
+
+    /// Returns a snapshot of the buffer's current content.
+    ///
+    /// This is linear in the number of lines, but doesn't copy their content,
+    /// which is reference-counted and shared with the mirror.
+    #[inline]
+    pub fn snapshot(&self) -> BufferSnapshot {
+        let state = self.state.borrow();
+        BufferSnapshot {
+            changedtick: state.changedtick,
+            lines: state.lines.iter().cloned().collect(),
+        }
+    }
+}
+
+impl MirrorState {
+    fn on_event(&mut self, event: BufEvent) {
+        if let BufEvent::Detach { .. } = event {
+            self.detached = true;
+            return;
+        }
+
+        // After an error the mirror can't be patched anymore, so it has to
+        // read the whole buffer again on the next change.
+        let res = match (&self.error, &event) {
+            (None, _) => self.apply(&event),
+            (
+                Some(_),
+                BufEvent::Bytes { buffer, .. }
+                | BufEvent::ChangedTick { buffer, .. }
+                | BufEvent::Reload { buffer },
+            ) => self.resync(buffer),
+            (Some(_), _) => return,
+        };
+
+        // If the text couldn't be read we fall back to reading the whole
+        // buffer, which is slower but always correct. If that fails too the
+        // mirror is stale until a later resync succeeds.
+        self.error = res.or_else(|_| self.resync(event.buffer())).err();
+    }
+
+    fn apply(&mut self, event: &BufEvent) -> Result<()> {
+        match event {
+            BufEvent::Bytes {
+                buffer,
+                start,
+                old_end,
+                new_end,
+                changedtick,
+                ..
+            } => {
+                self.changedtick = *changedtick;
+                let new_text = read_new_text(buffer, *start, *new_end)?;
+                apply_edit(&mut self.lines, *start, *old_end, new_text);
+                Ok(())
+            },
+            // Undo and redo restore all the text before sending the Bytes
+            // events of the edits they revert, so those were applied with the
+            // final text at intermediate positions. They end by sending this
+            // event, once the text is final.
+            BufEvent::ChangedTick { buffer, .. } => self.resync(buffer),
+            BufEvent::Reload { buffer } => self.resync(buffer),
+            _ => Ok(()),
+        }
+    }
+
+    fn resync(&mut self, buffer: &Buffer) -> Result<()> {
+        self.lines = read_lines(buffer)?;
+        self.changedtick = buffer.get_changedtick()?;
+        Ok(())
+    }
+}
+
+impl BufferSnapshot {
+    /// Returns the value of the buffer's `b:changedtick` when the snapshot
+    /// was taken.
+    #[inline]
+    pub fn changedtick(&self) -> u32 {
+        self.changedtick
+    }
+
+    /// Returns the line at the given index, if it exists.
+    #[inline]
+    pub fn line(&self, idx: usize) -> Option<&[u8]> {
+        self.lines.get(idx).map(|line| &**line)
+    }
+
+    /// Returns the number of lines in the snapshot.
+    #[inline]
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Returns an iterator over the lines of the snapshot.
+    #[inline]
+    pub fn lines(&self) -> impl ExactSizeIterator<Item = &[u8]> + '_ {
+        self.lines.iter().map(|line| &**line)
+    }
+
+    /// Returns the content of the snapshot, with lines joined by `\n`.
+    pub fn text(&self) -> Vec<u8> {
+        self.lines.join(&b'\n')
+    }
+}
+
+fn read_lines(buffer: &Buffer) -> Result<Vec<Arc<[u8]>>> {
+    Ok(buffer
+        .get_lines(.., true)?
+        .map(|line| Arc::from(line.as_bytes()))
+        .collect())
+}
+
+/// Reads the text inserted by an edit, given its start position and its
+/// relative new end.
+fn read_new_text(
+    buffer: &Buffer,
+    (Row0(start_row), ByteCol(start_col)): (Row0, ByteCol),
+    (new_end_row, new_end_col): (usize, usize),
+) -> Result<Vec<Vec<u8>>> {
+    let end_col =
+        if new_end_row == 0 { start_col + new_end_col } else { new_end_col };
+
+    Ok(buffer
+        .get_text(
+            Row0(start_row)..Row0(start_row + new_end_row),
+            ByteCol(start_col),
+            ByteCol(end_col),
+            &GetTextOpts::default(),
+        )?
+        .map(|line| line.as_bytes().to_owned())
+        .collect())
+}
+
+/// Replaces the text between `start` and the relative `old_end` position
+/// with `new_text`, which contains at least one line.
+fn apply_edit(
+    lines: &mut Vec<Arc<[u8]>>,
+    (Row0(start_row), ByteCol(start_col)): (Row0, ByteCol),
+    (old_end_row, old_end_col): (usize, usize),
+    mut new_text: Vec<Vec<u8>>,
+) {
+    let end_row = start_row + old_end_row;
+
+    let end_col =
+        if old_end_row == 0 { start_col + old_end_col } else { old_end_col };
+
+    let line = |row: usize| lines.get(row).map(|line| &**line).unwrap_or(&[]);
+
+    let start_line = line(start_row);
+    let prefix = &start_line[..start_col.min(start_line.len())];
+
+    let end_line = line(end_row);
+    let suffix = &end_line[end_col.min(end_line.len())..];
+
+    if new_text.is_empty() {
+        new_text.push(Vec::new());
+    }
+
+    new_text[0].splice(0..0, prefix.iter().copied());
+    new_text.last_mut().expect("not empty").extend_from_slice(suffix);
+
+    // Lines appended after the end of the buffer are reported as ending with
+    // a newline, which doesn't start a new line.
+    if end_row >= lines.len()
+        && new_text.len() > 1
+        && new_text.last().is_some_and(Vec::is_empty)
+    {
+        new_text.pop();
+    }
+
+    let replaced = start_row.min(lines.len())..(end_row + 1).min(lines.len());
+
+    lines.splice(replaced, new_text.into_iter().map(Arc::from));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &[&str]) -> Vec<Arc<[u8]>> {
+        text.iter().map(|line| Arc::from(line.as_bytes())).collect()
+    }
+
+    fn text(text: &[&str]) -> Vec<Vec<u8>> {
+        text.iter().map(|line| line.as_bytes().to_owned()).collect()
+    }
+
+    #[test]
+    fn apply_edit_within_line() {
+        let mut buf = lines(&["hello world"]);
+        apply_edit(&mut buf, (Row0(0), ByteCol(6)), (0, 5), text(&["there"]));
+        assert_eq!(buf, lines(&["hello there"]));
+    }
+
+    #[test]
+    fn apply_edit_insert_lines() {
+        let mut buf = lines(&["foo", "baz"]);
+        apply_edit(
+            &mut buf,
+            (Row0(0), ByteCol(3)),
+            (0, 0),
+            text(&["", "bar"]),
+        );
+        assert_eq!(buf, lines(&["foo", "bar", "baz"]));
+    }
+
+    #[test]
+    fn apply_edit_delete_lines() {
+        let mut buf = lines(&["foo", "bar", "baz"]);
+        apply_edit(&mut buf, (Row0(0), ByteCol(1)), (2, 1), text(&[""]));
+        assert_eq!(buf, lines(&["faz"]));
+    }
+
+    #[test]
+    fn apply_edit_append_line_at_end() {
+        let mut buf = lines(&["foo"]);
+        apply_edit(
+            &mut buf,
+            (Row0(1), ByteCol(0)),
+            (0, 0),
+            text(&["bar", ""]),
+        );
+        assert_eq!(buf, lines(&["foo", "bar"]));
+    }
+}
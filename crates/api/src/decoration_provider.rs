@@ -0,0 +1,229 @@
+use core::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::opts::DecorationProviderOpts;
+#[cfg(feature = "neovim-0-12")] // On 0.12 and Nightly.
+use crate::types::ByteCol;
+use crate::types::Row0;
+use crate::{Buffer, Result, Window};
+
+/// A decoration provider, registered with [`register_decoration_provider`].
+///
+/// Neovim calls the provider's methods while redrawing the screen, which is
+/// the place to set ephemeral extmarks. Every method has a default
+/// implementation that does nothing, so only the ones that are needed have
+/// to be implemented.
+///
+/// Like in all the callbacks called during a redraw, the methods can't
+/// change the buffer's text or the window layout, and shouldn't do any
+/// expensive work.
+pub trait DecorationProvider: 'static {
+    /// Called at the start of every redraw cycle. Returning `false` disables
+    /// the provider until the next one.
+    #[inline]
+    fn on_start(&mut self, changedtick: u32) -> bool {
+        let _ = changedtick;
+        true
+    }
+
+    /// Called once for every buffer being redrawn, before
+    /// [`on_win`](DecorationProvider::on_win).
+    #[inline]
+    fn on_buf(&mut self, buffer: Buffer, changedtick: u32) {
+        let _ = (buffer, changedtick);
+    }
+
+    /// Called when starting to redraw a window, with the range of its rows
+    /// that could be drawn. Returning `false` skips the calls to
+    /// [`on_line`](DecorationProvider::on_line) for the window.
+    #[inline]
+    fn on_win(
+        &mut self,
+        window: Window,
+        buffer: Buffer,
+        top_row: Row0,
+        bottom_row: Row0,
+    ) -> bool {
+        let _ = (window, buffer, top_row, bottom_row);
+        true
+    }
+
+    /// Called for every row of a window being redrawn.
+    #[inline]
+    fn on_line(&mut self, window: Window, buffer: Buffer, row: Row0) {
+        let _ = (window, buffer, row);
+    }
+
+    /// Called for every range of a buffer being redrawn in a window. The end
+    /// position is exclusive. Returning `false` skips the range.
+    #[cfg(feature = "neovim-0-12")] // On 0.12 and Nightly.
+    #[inline]
+    fn on_range(
+        &mut self,
+        window: Window,
+        buffer: Buffer,
+        start: (Row0, ByteCol),
+        end: (Row0, ByteCol),
+    ) -> bool {
+        let _ = (window, buffer, start, end);
+        true
+    }
+
+    /// Called at the end of every redraw cycle.
+    #[inline]
+    fn on_end(&mut self, changedtick: u32) {
+        let _ = changedtick;
+    }
+}
+
+thread_local! {
+    /// The number of providers registered with
+    /// [`register_decoration_provider`] for every namespace, used to tell
+    /// whether a handle's provider is still the current one.
+    static GENERATIONS: RefCell<HashMap<u32, u64>> = RefCell::default();
+}
+
+/// A handle to a [`DecorationProvider`] registered with
+/// [`register_decoration_provider`].
+///
+/// The provider is disabled when the handle is dropped. Use
+/// [`keep`](DecorationProviderHandle::keep) to leave it registered for the
+/// rest of the session.
+#[must_use = "the decoration provider is disabled when the handle is dropped"]
+#[derive(Debug)]
+pub struct DecorationProviderHandle {
+    ns_id: u32,
+    generation: u64,
+}
+
+/// Registers a [`DecorationProvider`] for the given namespace, replacing the
+/// one that was previously set for it, if any.
+///
+/// Dropping the handle of a replaced provider doesn't disable the one that
+/// replaced it. This only holds for providers registered with this function,
+/// not with [`set_decoration_provider`](crate::set_decoration_provider).
+///
+/// If a method is called while another one is still running, e.g. because
+/// it forced a nested redraw, the nested call is skipped.
+pub fn register_decoration_provider<P: DecorationProvider>(
+    ns_id: u32,
+    provider: P,
+) -> Result<DecorationProviderHandle> {
+    let provider = Rc::new(RefCell::new(provider));
+
+    let mut builder = DecorationProviderOpts::builder();
+
+    builder
+        .on_start({
+            let provider = Rc::clone(&provider);
+            move |(_, changedtick)| {
+                with_provider(&provider, true, |p| p.on_start(changedtick))
+            }
+        })
+        .on_buf({
+            let provider = Rc::clone(&provider);
+            move |(_, buffer, changedtick)| {
+                with_provider(&provider, (), |p| p.on_buf(buffer, changedtick))
+            }
+        })
+        .on_win({
+            let provider = Rc::clone(&provider);
+            move |(_, window, buffer, top_row, bottom_row)| {
+                with_provider(&provider, false, |p| {
+                    p.on_win(
+                        window,
+                        buffer,
+                        Row0(top_row as usize),
+                        Row0(bottom_row as usize),
+                    )
+                })
+            }
+        })
+        .on_line({
+            let provider = Rc::clone(&provider);
+            move |(_, window, buffer, row)| {
+                with_provider(&provider, (), |p| {
+                    p.on_line(window, buffer, Row0(row))
+                })
+            }
+        });
+
+    #[cfg(feature = "neovim-0-12")] // On 0.12 and Nightly.
+    builder.on_range({
+        let provider = Rc::clone(&provider);
+        move |(_, window, buffer, start_row, start_col, end_row, end_col)| {
+            with_provider(&provider, false, |p| {
+                p.on_range(
+                    window,
+                    buffer,
+                    (Row0(start_row), ByteCol(start_col)),
+                    (Row0(end_row), ByteCol(end_col)),
+                )
+            })
+        }
+    });
+
+    builder.on_end(move |(_, changedtick)| {
+        with_provider(&provider, (), |p| p.on_end(changedtick))
+    });
+
+    crate::set_decoration_provider(ns_id, &builder.build())?;
+
+    let generation = GENERATIONS.with_borrow_mut(|generations| {
+        let generation = generations.entry(ns_id).or_default();
+        *generation += 1;
+        *generation
+    });
+
+    Ok(DecorationProviderHandle { ns_id, generation })
+}
+
+/// Calls `fun` with the provider, or returns `default` if it's already
+/// borrowed by a method that's still running.
+#[inline]
+fn with_provider<P, R>(
+    provider: &RefCell<P>,
+    default: R,
+    fun: impl FnOnce(&mut P) -> R,
+) -> R {
+    match provider.try_borrow_mut() {
+        Ok(mut provider) => fun(&mut provider),
+        Err(_) => default,
+    }
+}
+
+impl DecorationProviderHandle {
+    /// Returns the namespace the provider is registered for.
+    #[inline]
+    pub fn namespace(&self) -> u32 {
+        self.ns_id
+    }
+
+    /// Disables the provider. This is the same as dropping the handle.
+    #[inline]
+    pub fn disable(self) {}
+
+    /// Consumes the handle without disabling the provider.
+    #[inline]
+    pub fn keep(self) {
+        core::mem::forget(self);
+    }
+}
+
+impl Drop for DecorationProviderHandle {
+    fn drop(&mut self) {
+        let is_current = GENERATIONS.with_borrow(|generations| {
+            generations.get(&self.ns_id) == Some(&self.generation)
+        });
+
+        // The provider was replaced by another one, which we leave alone.
+        if !is_current {
+            return;
+        }
+
+        // Setting a provider with no callbacks releases the previous ones.
+        let opts = DecorationProviderOpts::default();
+        let _ = crate::set_decoration_provider(self.ns_id, &opts);
+    }
+}
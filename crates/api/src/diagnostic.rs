@@ -0,0 +1,326 @@
+//! Bindings to the [`vim.diagnostic`][1] Lua module.
+//!
+//! Namespaces can be created with [`create_namespace`](crate::create_namespace).
+//!
+//! [1]: https://neovim.io/doc/user/diagnostic.html
+
+use std::result::Result as StdResult;
+
+use serde::{Deserialize, de};
+use serde_repr::Deserialize_repr;
+use types::{
+    Array,
+    Dictionary,
+    Integer,
+    Object,
+    conversion::{self, FromObject},
+    serde::Deserializer,
+};
+
+use crate::types::{ByteCol, Row0};
+use crate::{Buffer, Result, utils::call_vim_fn};
+
+/// A diagnostic, mapped to a Lua [`vim.Diagnostic`][1].
+///
+/// Line and column numbers are 0-indexed, and the end column is exclusive.
+///
+/// [1]: https://neovim.io/doc/user/diagnostic.html#vim.Diagnostic
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+pub struct Diagnostic {
+    /// The buffer the diagnostic belongs to. Set by Neovim.
+    #[serde(default)]
+    pub bufnr: Option<Buffer>,
+
+    /// The line number of the start of the diagnostic.
+    pub lnum: Row0,
+
+    /// The line number of the end of the diagnostic. Defaults to
+    /// [`lnum`](Diagnostic::lnum).
+    #[serde(default)]
+    pub end_lnum: Option<Row0>,
+
+    /// The column of the start of the diagnostic.
+    pub col: ByteCol,
+
+    /// The column of the end of the diagnostic. Defaults to
+    /// [`col`](Diagnostic::col).
+    #[serde(default)]
+    pub end_col: Option<ByteCol>,
+
+    /// The severity of the diagnostic.
+    #[serde(default)]
+    pub severity: Severity,
+
+    /// The diagnostic text.
+    pub message: String,
+
+    /// The source of the diagnostic, e.g. the name of the linter.
+    #[serde(default)]
+    pub source: Option<String>,
+
+    /// The diagnostic code.
+    #[serde(default, deserialize_with = "code")]
+    pub code: Option<String>,
+
+    /// The namespace the diagnostic belongs to. Set by Neovim.
+    #[serde(default)]
+    pub namespace: Option<u32>,
+
+    /// Arbitrary data attached to the diagnostic.
+    #[serde(default)]
+    pub user_data: Option<Object>,
+}
+
+/// The severity of a [`Diagnostic`], mapped to
+/// [`vim.diagnostic.severity`][1].
+///
+/// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.severity
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Deserialize_repr,
+)]
+#[repr(u8)]
+pub enum Severity {
+    #[default]
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Hint = 4,
+}
+
+/// Options passed to [`config`], see [`vim.diagnostic.Opts`][1] for more
+/// infos. Options that are not set keep their current value.
+///
+/// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.Opts
+#[derive(Clone, Debug, Default)]
+pub struct DiagnosticConfig {
+    severity_sort: Option<bool>,
+    signs: Option<bool>,
+    underline: Option<bool>,
+    update_in_insert: Option<bool>,
+    virtual_lines: Option<bool>,
+    virtual_text: Option<bool>,
+}
+
+impl Diagnostic {
+    /// Creates a new diagnostic starting at the given position.
+    #[inline]
+    pub fn new(
+        lnum: Row0,
+        col: ByteCol,
+        severity: Severity,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            lnum,
+            col,
+            severity,
+            message: message.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the diagnostic code.
+    #[inline]
+    pub fn code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Sets the end position of the diagnostic.
+    #[inline]
+    pub fn end(mut self, end_lnum: Row0, end_col: ByteCol) -> Self {
+        self.end_lnum = Some(end_lnum);
+        self.end_col = Some(end_col);
+        self
+    }
+
+    /// Sets the source of the diagnostic.
+    #[inline]
+    pub fn source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Attaches arbitrary data to the diagnostic.
+    #[inline]
+    pub fn user_data(mut self, user_data: impl Into<Object>) -> Self {
+        self.user_data = Some(user_data.into());
+        self
+    }
+}
+
+impl From<Diagnostic> for Dictionary {
+    fn from(diagnostic: Diagnostic) -> Self {
+        let mut dict = Dictionary::new();
+        dict.insert("lnum", diagnostic.lnum);
+        if let Some(end_lnum) = diagnostic.end_lnum {
+            dict.insert("end_lnum", end_lnum);
+        }
+        dict.insert("col", diagnostic.col);
+        if let Some(end_col) = diagnostic.end_col {
+            dict.insert("end_col", end_col);
+        }
+        dict.insert("severity", diagnostic.severity);
+        dict.insert("message", diagnostic.message);
+        if let Some(source) = diagnostic.source {
+            dict.insert("source", source);
+        }
+        if let Some(code) = diagnostic.code {
+            dict.insert("code", code);
+        }
+        if let Some(user_data) = diagnostic.user_data {
+            dict.insert("user_data", user_data);
+        }
+        dict
+    }
+}
+
+impl From<Diagnostic> for Object {
+    #[inline]
+    fn from(diagnostic: Diagnostic) -> Self {
+        Dictionary::from(diagnostic).into()
+    }
+}
+
+impl FromObject for Diagnostic {
+    #[inline]
+    fn from_object(obj: Object) -> StdResult<Self, conversion::Error> {
+        Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
+    }
+}
+
+impl From<Severity> for Object {
+    #[inline]
+    fn from(severity: Severity) -> Self {
+        (severity as Integer).into()
+    }
+}
+
+macro_rules! config_setters {
+    ($($(#[$doc:meta])* $name:ident),* $(,)?) => {
+        $(
+            $(#[$doc])*
+            #[inline]
+            pub fn $name(mut self, $name: bool) -> Self {
+                self.$name = Some($name);
+                self
+            }
+        )*
+    };
+}
+
+impl DiagnosticConfig {
+    config_setters!(
+        /// Whether to sort diagnostics by severity.
+        severity_sort,
+        /// Whether to show diagnostics in the sign column.
+        signs,
+        /// Whether to underline diagnostics.
+        underline,
+        /// Whether to update diagnostics in Insert mode.
+        update_in_insert,
+        /// Whether to show diagnostics as virtual lines.
+        virtual_lines,
+        /// Whether to show diagnostics as virtual text.
+        virtual_text,
+    );
+}
+
+impl From<&DiagnosticConfig> for Dictionary {
+    fn from(config: &DiagnosticConfig) -> Self {
+        Dictionary::from_iter([
+            ("severity_sort", config.severity_sort),
+            ("signs", config.signs),
+            ("underline", config.underline),
+            ("update_in_insert", config.update_in_insert),
+            ("virtual_lines", config.virtual_lines),
+            ("virtual_text", config.virtual_text),
+        ])
+    }
+}
+
+/// Binding to [`vim.diagnostic.config()`][1].
+///
+/// Configures diagnostics globally, or for the given namespace.
+///
+/// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.config()
+pub fn config(
+    namespace: Option<u32>,
+    config: &DiagnosticConfig,
+) -> Result<()> {
+    call_vim_fn(
+        &[c"diagnostic", c"config"],
+        (Dictionary::from(config), namespace),
+    )
+}
+
+/// Binding to [`vim.diagnostic.get()`][1].
+///
+/// Returns the diagnostics of the given namespace and buffer, or of all
+/// namespaces and buffers if `None`.
+///
+/// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.get()
+pub fn get(
+    namespace: Option<u32>,
+    buffer: Option<&Buffer>,
+) -> Result<Vec<Diagnostic>> {
+    let opts = Dictionary::from_iter([("namespace", namespace)]);
+    let diagnostics: Array =
+        call_vim_fn(&[c"diagnostic", c"get"], (buffer.cloned(), opts))?;
+    Vec::from_object(diagnostics.into()).map_err(Into::into)
+}
+
+/// Binding to [`vim.diagnostic.reset()`][1].
+///
+/// Removes the diagnostics of the given namespace and buffer, or of all
+/// namespaces and buffers if `None`.
+///
+/// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.reset()
+pub fn reset(namespace: Option<u32>, buffer: Option<&Buffer>) -> Result<()> {
+    call_vim_fn(&[c"diagnostic", c"reset"], (namespace, buffer.cloned()))
+}
+
+/// Binding to [`vim.diagnostic.set()`][1].
+///
+/// Replaces the diagnostics of the given namespace and buffer.
+///
+/// [1]: https://neovim.io/doc/user/diagnostic.html#vim.diagnostic.set()
+pub fn set(
+    namespace: u32,
+    buffer: &Buffer,
+    diagnostics: Vec<Diagnostic>,
+) -> Result<()> {
+    let diagnostics =
+        diagnostics.into_iter().map(Object::from).collect::<Array>();
+    call_vim_fn(
+        &[c"diagnostic", c"set"],
+        (namespace, buffer.clone(), diagnostics),
+    )
+}
+
+/// Diagnostic codes can be either strings or integers.
+fn code<'de, D>(deserializer: D) -> StdResult<Option<String>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Code {
+        Int(Integer),
+        Str(String),
+    }
+
+    Ok(Option::<Code>::deserialize(deserializer)?.map(|code| match code {
+        Code::Int(n) => n.to_string(),
+        Code::Str(s) => s,
+    }))
+}
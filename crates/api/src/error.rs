@@ -5,6 +5,7 @@ use thiserror::Error as ThisError;
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Clone, Debug, ThisError, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum Error {
     #[error(transparent)]
     FromInt(#[from] std::num::TryFromIntError),
@@ -12,6 +13,16 @@ pub enum Error {
     #[error(transparent)]
     FromUtf8(#[from] std::string::FromUtf8Error),
 
+    /// A line of a buffer is not valid UTF-8.
+    #[error("line {line} is not valid UTF-8: {source}")]
+    LineFromUtf8 {
+        /// The 0-indexed line number.
+        line: usize,
+
+        /// The conversion error.
+        source: std::string::FromUtf8Error,
+    },
+
     #[error(transparent)]
     Nvim(#[from] types::Error),
 
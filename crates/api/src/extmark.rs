@@ -63,11 +63,11 @@ impl Buffer {
         &mut self,
         ns_id: u32,
         hl_group: &str,
-        line: usize,
+        line: Row0,
         byte_range: R,
     ) -> Result<i64>
     where
-        R: RangeBounds<usize>,
+        R: RangeBounds<ByteCol>,
     {
         let hl_group = nvim::String::from(hl_group);
         let mut err = nvim::Error::new();
@@ -77,7 +77,7 @@ impl Buffer {
                 self.0,
                 ns_id.into(),
                 hl_group.as_nvim_str(),
-                line as Integer,
+                line.0 as Integer,
                 start,
                 end,
                 &mut err,
@@ -100,7 +100,7 @@ impl Buffer {
         line_range: R,
     ) -> Result<()>
     where
-        R: RangeBounds<usize>,
+        R: RangeBounds<Row0>,
     {
         let mut err = nvim::Error::new();
         let (start, end) = utils::range_to_limits(line_range);
@@ -155,7 +155,7 @@ impl Buffer {
         ns_id: u32,
         extmark_id: u32,
         opts: &GetExtmarkByIdOpts,
-    ) -> Result<(usize, usize, Option<ExtmarkInfos>)> {
+    ) -> Result<(Row0, ByteCol, Option<ExtmarkInfos>)> {
         let mut err = nvim::Error::new();
         let tuple = unsafe {
             nvim_buf_get_extmark_by_id(
@@ -175,10 +175,9 @@ impl Buffer {
             }
 
             let mut iter = tuple.into_iter();
-            let row =
-                usize::from_object(iter.next().expect("row is present"))?;
+            let row = Row0::from_object(iter.next().expect("row is present"))?;
             let col =
-                usize::from_object(iter.next().expect("col is present"))?;
+                ByteCol::from_object(iter.next().expect("col is present"))?;
             let infos =
                 iter.next().map(ExtmarkInfos::from_object).transpose()?;
             Ok((row, col, infos))
@@ -202,7 +201,7 @@ impl Buffer {
         end: ExtmarkPosition,
         opts: &GetExtmarksOpts,
     ) -> Result<
-        impl SuperIterator<(u32, usize, usize, Option<ExtmarkInfos>)> + use<NsId>,
+        impl SuperIterator<(u32, Row0, ByteCol, Option<ExtmarkInfos>)> + use<NsId>,
     > {
         let mut err = nvim::Error::new();
         let extmarks = unsafe {
@@ -225,11 +224,11 @@ impl Buffer {
                     let id =
                         u32::from_object(iter.next().expect("id is present"))
                             .unwrap();
-                    let row = usize::from_object(
+                    let row = Row0::from_object(
                         iter.next().expect("row is present"),
                     )
                     .unwrap();
-                    let col = usize::from_object(
+                    let col = ByteCol::from_object(
                         iter.next().expect("col is present"),
                     )
                     .unwrap();
@@ -253,8 +252,8 @@ impl Buffer {
     pub fn set_extmark(
         &mut self,
         ns_id: u32,
-        line: usize,
-        col: usize,
+        line: Row0,
+        col: ByteCol,
         opts: &SetExtmarkOpts,
     ) -> Result<u32> {
         let mut err = nvim::Error::new();
@@ -262,8 +261,8 @@ impl Buffer {
             nvim_buf_set_extmark(
                 self.0,
                 ns_id as Integer,
-                line as Integer,
-                col as Integer,
+                line.0 as Integer,
+                col.0 as Integer,
                 opts,
                 &mut err,
             )
@@ -0,0 +1,406 @@
+use types::{Array, Integer};
+
+use crate::opts::{CreateAutocmdOpts, OptionOpts, WinTextHeightOpts};
+use crate::types::{
+    AutocmdCallbackArgs,
+    WindowAnchor,
+    WindowBorder,
+    WindowConfig,
+    WindowRelativeTo,
+    WindowStyle,
+    WindowTitle,
+};
+use crate::{Buffer, Result, Window};
+
+/// A builder for floating windows, placing and sizing them from the
+/// dimensions of the editor instead of raw coordinates.
+///
+/// The sizes always exclude the border, and are clamped so that the window
+/// fits in the editor.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::api::{self, FloatBuilder};
+/// use nvim_oxi::api::types::WindowBorder;
+///
+/// let buffer = api::create_buf(false, true)?;
+///
+/// let window = FloatBuilder::centered(80, 60)
+///     .border(WindowBorder::Rounded)
+///     .open(&buffer, true)?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct FloatBuilder {
+    placement: Placement,
+    size: Option<(u32, u32)>,
+    fit_content: bool,
+    auto_resize: bool,
+    border: Option<WindowBorder>,
+    style: Option<WindowStyle>,
+    title: Option<WindowTitle>,
+    focusable: Option<bool>,
+    zindex: Option<u32>,
+}
+
+/// Where a [`FloatBuilder`] places its window.
+#[derive(Clone, Debug)]
+enum Placement {
+    Centered { width_percent: u8, height_percent: u8 },
+    AtCursor,
+    Beside(Window),
+    BottomRight,
+}
+
+/// The number of rows and columns available to floating windows.
+#[derive(Copy, Clone, Debug)]
+struct EditorSize {
+    width: u32,
+    height: u32,
+}
+
+impl FloatBuilder {
+    #[inline]
+    fn new(placement: Placement) -> Self {
+        Self {
+            placement,
+            size: None,
+            fit_content: false,
+            auto_resize: false,
+            border: None,
+            style: None,
+            title: None,
+            focusable: None,
+            zindex: None,
+        }
+    }
+
+    /// Places the window at the center of the editor, taking up the given
+    /// percentages of its width and height.
+    #[inline]
+    pub fn centered(width_percent: u8, height_percent: u8) -> Self {
+        Self::new(Placement::Centered {
+            width_percent: width_percent.min(100),
+            height_percent: height_percent.min(100),
+        })
+    }
+
+    /// Places the window right below the cursor, or above it if there isn't
+    /// enough space below. The window is sized to fit the buffer's content
+    /// unless a [`size`](FloatBuilder::size) is given.
+    #[inline]
+    pub fn at_cursor() -> Self {
+        Self::new(Placement::AtCursor)
+    }
+
+    /// Places the window to the right of the given one, or to its left if
+    /// there isn't enough space on the right, aligned with its top. The
+    /// window is sized to fit the buffer's content unless a
+    /// [`size`](FloatBuilder::size) is given.
+    #[inline]
+    pub fn beside(window: &Window) -> Self {
+        Self::new(Placement::Beside(window.clone()))
+    }
+
+    /// Places the window in the bottom right corner of the editor, above
+    /// the command line. The window is sized to fit the buffer's content
+    /// unless a [`size`](FloatBuilder::size) is given.
+    #[inline]
+    pub fn bottom_right() -> Self {
+        Self::new(Placement::BottomRight)
+    }
+
+    /// Sets the size of the window. When fitting the content, this is the
+    /// maximum size instead.
+    #[inline]
+    pub fn size(&mut self, width: u32, height: u32) -> &mut Self {
+        self.size = Some((width, height));
+        self
+    }
+
+    /// Whether to size the window to fit the buffer's content, up to the
+    /// size it would otherwise have. See
+    /// [`auto_resize`](FloatBuilder::auto_resize) to keep it fitting after
+    /// the content changes.
+    #[inline]
+    pub fn fit_content(&mut self, fit_content: bool) -> &mut Self {
+        self.fit_content = fit_content;
+        self
+    }
+
+    /// Whether to [`resize`](FloatBuilder::resize) the window every time its
+    /// buffer's content or the size of the editor or of a window changes,
+    /// until the window is closed.
+    #[inline]
+    pub fn auto_resize(&mut self, auto_resize: bool) -> &mut Self {
+        self.auto_resize = auto_resize;
+        self
+    }
+
+    /// Style of the window border.
+    #[inline]
+    pub fn border(&mut self, border: WindowBorder) -> &mut Self {
+        self.border = Some(border);
+        self
+    }
+
+    /// Configures the appearance of the window.
+    #[inline]
+    pub fn style(&mut self, style: WindowStyle) -> &mut Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Title shown in the window's border.
+    #[inline]
+    pub fn title(&mut self, title: WindowTitle) -> &mut Self {
+        self.title = Some(title);
+        self
+    }
+
+    /// Enable focus by user actions like mouse events.
+    #[inline]
+    pub fn focusable(&mut self, focusable: bool) -> &mut Self {
+        self.focusable = Some(focusable);
+        self
+    }
+
+    /// Stacking order. Windows with higher `zindex` go in front of windows
+    /// with lower indices.
+    #[inline]
+    pub fn zindex(&mut self, zindex: u32) -> &mut Self {
+        self.zindex = Some(zindex);
+        self
+    }
+
+    /// Returns the configuration of a window showing the given buffer.
+    ///
+    /// The height needed to fit the content is estimated from the number of
+    /// lines, since it can only be measured in an open window.
+    #[inline]
+    pub fn config(&self, buffer: &Buffer) -> Result<WindowConfig> {
+        self.compute_config(buffer, None)
+    }
+
+    /// Opens a floating window showing the given buffer.
+    pub fn open(&self, buffer: &Buffer, enter: bool) -> Result<Window> {
+        let mut window =
+            crate::open_win(buffer, enter, &self.config(buffer)?)?;
+
+        if self.fits_content() {
+            self.resize(&mut window)?;
+        }
+
+        if self.auto_resize {
+            self.resize_on_changes(buffer, &window)?;
+        }
+
+        Ok(window)
+    }
+
+    /// Recomputes the size and position of a window opened by this builder,
+    /// e.g. after its buffer's content or the size of the editor changed.
+    pub fn resize(&self, window: &mut Window) -> Result<()> {
+        let buffer = window.get_buf()?;
+        let config = self.compute_config(&buffer, Some(window))?;
+        window.set_config(&config)
+    }
+
+    /// Creates the autocommands resizing the window, which delete themselves
+    /// once they're triggered after the window is closed.
+    fn resize_on_changes(
+        &self,
+        buffer: &Buffer,
+        window: &Window,
+    ) -> Result<()> {
+        let callback = {
+            let builder = self.clone();
+            let window = window.clone();
+            move |_: AutocmdCallbackArgs| {
+                if !window.is_valid() {
+                    return Ok(true);
+                }
+                builder.resize(&mut window.clone())?;
+                Ok::<_, crate::Error>(false)
+            }
+        };
+
+        let opts = CreateAutocmdOpts::builder()
+            .buffer(buffer.clone())
+            .callback(callback.clone())
+            .build();
+        crate::create_autocmd(["TextChanged", "TextChangedI"], &opts)?;
+
+        let opts = CreateAutocmdOpts::builder().callback(callback).build();
+        crate::create_autocmd(["VimResized", "WinResized"], &opts)?;
+
+        Ok(())
+    }
+
+    /// Whether the window is sized from the buffer's content.
+    #[inline]
+    fn fits_content(&self) -> bool {
+        self.fit_content
+            || (self.size.is_none()
+                && !matches!(self.placement, Placement::Centered { .. }))
+    }
+
+    fn compute_config(
+        &self,
+        buffer: &Buffer,
+        window: Option<&Window>,
+    ) -> Result<WindowConfig> {
+        let editor = EditorSize::current()?;
+        let (border_width, border_height) = self.border_size();
+
+        let max_width = editor.width.saturating_sub(border_width).max(1);
+        let max_height = editor.height.saturating_sub(border_height).max(1);
+
+        let (width, height) = match (&self.size, &self.placement) {
+            (Some(size), _) => *size,
+            (None, Placement::Centered { width_percent, height_percent }) => (
+                max_width * *width_percent as u32 / 100,
+                max_height * *height_percent as u32 / 100,
+            ),
+            (None, Placement::Beside(beside)) => {
+                (max_width, beside.get_height()?)
+            },
+            (None, _) => (max_width, max_height),
+        };
+
+        let mut width = width.clamp(1, max_width);
+        let mut height = height.clamp(1, max_height);
+
+        if self.fits_content() {
+            width = width.min(content_width(buffer)?.max(1));
+            height = height.min(content_height(buffer, window)?.max(1));
+        }
+
+        let (total_width, total_height) =
+            (width + border_width, height + border_height);
+
+        let mut builder = WindowConfig::builder();
+
+        builder.width(width).height(height);
+
+        match &self.placement {
+            Placement::Centered { .. } => {
+                builder
+                    .relative(WindowRelativeTo::Editor)
+                    .row(editor.height.saturating_sub(total_height) / 2)
+                    .col(editor.width.saturating_sub(total_width) / 2);
+            },
+
+            Placement::AtCursor => {
+                let cursor_row = crate::call_function::<_, Integer>(
+                    "screenrow",
+                    Array::new(),
+                )?;
+                let space_below = editor.height as Integer - cursor_row;
+
+                builder.relative(WindowRelativeTo::Cursor).col(0);
+
+                if total_height as Integer <= space_below {
+                    builder.anchor(WindowAnchor::NorthWest).row(1);
+                } else {
+                    builder.anchor(WindowAnchor::SouthWest).row(0);
+                }
+            },
+
+            Placement::Beside(beside) => {
+                let (_, beside_col) = beside.get_position()?;
+                let beside_width = beside.get_width()?;
+                let space_right = editor
+                    .width
+                    .saturating_sub(beside_col as u32 + beside_width);
+
+                let col = if total_width > space_right
+                    && total_width <= beside_col as u32
+                {
+                    -(total_width as Integer)
+                } else {
+                    beside_width as Integer
+                };
+
+                builder
+                    .relative(WindowRelativeTo::Window(beside.clone()))
+                    .anchor(WindowAnchor::NorthWest)
+                    .row(0)
+                    .col(col as f64);
+            },
+
+            Placement::BottomRight => {
+                builder
+                    .relative(WindowRelativeTo::Editor)
+                    .anchor(WindowAnchor::SouthEast)
+                    .row(editor.height)
+                    .col(editor.width);
+            },
+        }
+
+        if let Some(border) = &self.border {
+            builder.border(border.clone());
+        }
+
+        if let Some(style) = self.style {
+            builder.style(style);
+        }
+
+        if let Some(title) = &self.title {
+            builder.title(title.clone());
+        }
+
+        if let Some(focusable) = self.focusable {
+            builder.focusable(focusable);
+        }
+
+        if let Some(zindex) = self.zindex {
+            builder.zindex(zindex);
+        }
+
+        Ok(builder.build())
+    }
+
+    /// Returns the number of columns and rows taken up by the border.
+    fn border_size(&self) -> (u32, u32) {
+        match &self.border {
+            None | Some(WindowBorder::None) => (0, 0),
+            Some(WindowBorder::Shadow) => (1, 1),
+            Some(_) => (2, 2),
+        }
+    }
+}
+
+impl EditorSize {
+    /// Returns the size of the editor, excluding the command line.
+    fn current() -> Result<Self> {
+        let opts = OptionOpts::default();
+        let columns = crate::get_option_value::<u32>("columns", &opts)?;
+        let lines = crate::get_option_value::<u32>("lines", &opts)?;
+        let cmdheight = crate::get_option_value::<u32>("cmdheight", &opts)?;
+        Ok(Self { width: columns, height: lines.saturating_sub(cmdheight) })
+    }
+}
+
+/// Returns the display width of the widest line of the buffer, expanding
+/// tabs according to its `'tabstop'`.
+fn content_width(buffer: &Buffer) -> Result<u32> {
+    let width: Integer = buffer.call(|()| {
+        crate::eval::<Integer>(
+            "max(map(getline(1, '$'), 'strdisplaywidth(v:val)'))",
+        )
+    })?;
+    Ok(width.try_into()?)
+}
+
+/// Returns the number of screen lines needed to show the whole buffer in the
+/// window, or the number of lines of the buffer if it's not shown yet.
+fn content_height(buffer: &Buffer, window: Option<&Window>) -> Result<u32> {
+    match window {
+        Some(window) => {
+            let opts = WinTextHeightOpts::default();
+            window.text_height(&opts).map(|height| height.all)
+        },
+        None => Ok(buffer.line_count()?.try_into()?),
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use types::{self as nvim, Integer, conversion::FromObject};
+
+use crate::ffi::vim::nvim_get_hl;
+use crate::opts::{GetHighlightOpts, SetHighlightOpts};
+use crate::types::{GetHlInfos, Highlight, HighlightInfos};
+use crate::{Error, Result};
+
+/// The highlight groups defined in a namespace, returned by
+/// [`export_colorscheme`].
+///
+/// It serializes to a map from group names to [`Highlight`]s, and can be
+/// reapplied to any namespace with [`apply`](Colorscheme::apply).
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Colorscheme {
+    /// The highlight groups, sorted by name.
+    pub groups: BTreeMap<String, Highlight>,
+}
+
+impl Colorscheme {
+    /// Defines all the groups in the given namespace, using
+    /// [`set_hl`](crate::set_hl).
+    pub fn apply(&self, ns_id: u32) -> Result<()> {
+        for (name, hl) in &self.groups {
+            crate::set_hl(ns_id, name, &SetHighlightOpts::from(hl))?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the effective definition of a highlight group in the global
+/// namespace, following its links until a group that isn't linked.
+///
+/// Fails if there's no group with the given name.
+pub fn resolve_hl(name: &str) -> Result<Highlight> {
+    let exists = crate::call_function::<_, Integer>("hlexists", (name,))?;
+
+    if exists == 0 {
+        return Err(Error::custom(format!(
+            "no highlight group named {name:?}"
+        )));
+    }
+
+    let opts = GetHighlightOpts::builder()
+        .name(name)
+        .link(false)
+        .create(false)
+        .build();
+
+    // Since a name is given Neovim always returns a single definition, so
+    // this doesn't go through `get_hl`, which has to guess it.
+    let mut err = nvim::Error::new();
+    let dict = unsafe { nvim_get_hl(0, &opts, types::arena(), &mut err) };
+    if err.is_err() {
+        return Err(err.into());
+    }
+
+    Ok(HighlightInfos::from_object(dict.into())?.into())
+}
+
+/// Returns all the highlight groups defined in the given namespace, with
+/// linked groups kept as links.
+///
+/// Use a namespace of `0` to export the global highlight groups, e.g. the
+/// ones defined by the current colorscheme.
+pub fn export_colorscheme(ns_id: u32) -> Result<Colorscheme> {
+    let groups = match crate::get_hl(ns_id, &GetHighlightOpts::default())? {
+        GetHlInfos::Map(groups) => groups
+            .map(|(name, infos)| (name.to_string_lossy().into(), infos.into()))
+            .collect(),
+
+        // Neovim returns an empty dictionary for a namespace without groups.
+        GetHlInfos::Single(_) => BTreeMap::new(),
+    };
+
+    Ok(Colorscheme { groups })
+}
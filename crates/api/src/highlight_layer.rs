@@ -0,0 +1,255 @@
+use core::ops::Range;
+use std::collections::HashMap;
+
+use crate::opts::{GetExtmarksOpts, SetExtmarkOpts};
+use crate::types::{
+    ByteCol,
+    ExtmarkInfos,
+    ExtmarkPosition,
+    HighlightSpan,
+    OneOrMore,
+    Row0,
+};
+use crate::{Buffer, Result};
+
+/// A set of highlights in a buffer, backed by the extmarks of a namespace.
+///
+/// Every update takes the full set of spans that should be highlighted and
+/// only adds and deletes the extmarks that differ from the current ones, so
+/// the highlights that didn't change are left untouched. Since extmarks
+/// follow the edits made to the buffer, a span is compared with the current
+/// position of its extmark.
+///
+/// The layer owns its namespace in the buffer: any other extmark in it is
+/// deleted on the next update, and the namespace is cleared when the layer is
+/// dropped. Spans that go past the end of their line or of the buffer are
+/// clamped to it.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::api::{self, Buffer, HighlightLayer};
+/// use nvim_oxi::api::types::{ByteCol, HighlightSpan, Row0};
+///
+/// let ns_id = api::create_namespace("my-semantic-tokens");
+/// let mut layer = HighlightLayer::new(&Buffer::current(), ns_id);
+///
+/// layer.update([
+///     HighlightSpan::new((Row0(0), ByteCol(0)), (Row0(0), ByteCol(2)), "Keyword"),
+///     HighlightSpan::new((Row0(0), ByteCol(3)), (Row0(0), ByteCol(7)), "Function"),
+/// ])?;
+/// ```
+#[derive(Debug)]
+pub struct HighlightLayer {
+    buffer: Buffer,
+    ns_id: u32,
+}
+
+impl HighlightLayer {
+    /// Creates a new layer using the given namespace.
+    #[inline]
+    pub fn new(buffer: &Buffer, ns_id: u32) -> Self {
+        Self { buffer: buffer.clone(), ns_id }
+    }
+
+    /// Returns the buffer the layer is in.
+    #[inline]
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Returns the namespace of the layer's extmarks.
+    #[inline]
+    pub fn namespace(&self) -> u32 {
+        self.ns_id
+    }
+
+    /// Makes the highlights of the whole buffer match the given spans.
+    ///
+    /// If a highlight can't be added, the layer is left as it was and the
+    /// error is returned.
+    #[inline]
+    pub fn update(
+        &mut self,
+        spans: impl IntoIterator<Item = HighlightSpan>,
+    ) -> Result<()> {
+        let rows = Row0(0)..Row0(self.buffer.line_count()?);
+        self.update_rows_unchecked(rows, spans.into_iter().collect())
+    }
+
+    /// Like [`update`](HighlightLayer::update), but only touches the spans
+    /// starting in the given rows, e.g. the ones visible in a window. Spans
+    /// starting outside of them are ignored.
+    pub fn update_rows(
+        &mut self,
+        rows: Range<Row0>,
+        spans: impl IntoIterator<Item = HighlightSpan>,
+    ) -> Result<()> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+
+        let spans = spans
+            .into_iter()
+            .filter(|span| rows.contains(&span.start.0))
+            .collect();
+
+        self.update_rows_unchecked(rows, spans)
+    }
+
+    /// Removes all the highlights of the layer.
+    #[inline]
+    pub fn clear(&mut self) -> Result<()> {
+        self.buffer.clear_namespace(self.ns_id, ..)
+    }
+
+    /// Updates the extmarks starting in the given rows, assuming that all
+    /// the spans start in them.
+    fn update_rows_unchecked(
+        &mut self,
+        rows: Range<Row0>,
+        spans: Vec<HighlightSpan>,
+    ) -> Result<()> {
+        let spans = clamp_spans(&self.buffer, spans)?;
+
+        let opts = GetExtmarksOpts::builder().details(true).build();
+
+        // The end position is inclusive, so the extmarks starting at the
+        // beginning of the next row are skipped below.
+        let extmarks = self.buffer.get_extmarks(
+            self.ns_id,
+            ExtmarkPosition::ByTuple((rows.start, ByteCol(0))),
+            ExtmarkPosition::ByTuple((rows.end, ByteCol(0))),
+            &opts,
+        )?;
+
+        let mut current = HashMap::<HighlightSpan, Vec<u32>>::new();
+        let mut stale = Vec::new();
+
+        for (id, row, col, infos) in extmarks {
+            if row >= rows.end {
+                continue;
+            }
+
+            match infos.and_then(|infos| extmark_span(row, col, infos)) {
+                Some(span) => current.entry(span).or_default().push(id),
+                None => stale.push(id),
+            }
+        }
+
+        let mut missing = Vec::new();
+
+        for span in spans {
+            // Keep an extmark that already highlights the span, if any.
+            if current.get_mut(&span).and_then(Vec::pop).is_none() {
+                missing.push(span);
+            }
+        }
+
+        stale.extend(current.into_values().flatten());
+
+        // Add the missing highlights before deleting the stale ones, so that
+        // a failure leaves the layer as it was.
+        let mut added = Vec::with_capacity(missing.len());
+
+        for span in &missing {
+            match self.add(span) {
+                Ok(id) => added.push(id),
+                Err(err) => {
+                    for id in added {
+                        let _ = self.buffer.del_extmark(self.ns_id, id);
+                    }
+                    return Err(err);
+                },
+            }
+        }
+
+        for id in stale {
+            self.buffer.del_extmark(self.ns_id, id)?;
+        }
+
+        Ok(())
+    }
+
+    /// Adds an extmark highlighting the given span.
+    fn add(&mut self, span: &HighlightSpan) -> Result<u32> {
+        let opts = SetExtmarkOpts::builder()
+            .end_row(span.end.0)
+            .end_col(span.end.1)
+            .hl_group(span.hl_group.as_str())
+            .priority(span.priority)
+            .strict(false)
+            .build();
+
+        let (row, col) = span.start;
+        self.buffer.set_extmark(self.ns_id, row, col, &opts)
+    }
+}
+
+impl Drop for HighlightLayer {
+    fn drop(&mut self) {
+        // The extmarks are already gone if the buffer was deleted.
+        if self.buffer.is_valid() {
+            let _ = self.clear();
+        }
+    }
+}
+
+/// Clamps the spans to the content of the buffer, like Neovim does when
+/// creating their extmarks, so that they can be compared with the existing
+/// ones.
+fn clamp_spans(
+    buffer: &Buffer,
+    spans: Vec<HighlightSpan>,
+) -> Result<Vec<HighlightSpan>> {
+    let last_row = buffer.line_count()?.saturating_sub(1);
+    let mut line_lens = HashMap::<usize, usize>::new();
+
+    let mut clamp = |(Row0(row), ByteCol(col))| -> Result<(Row0, ByteCol)> {
+        let row = row.min(last_row);
+
+        let len = match line_lens.get(&row) {
+            Some(&len) => len,
+            None => {
+                let len = buffer
+                    .get_lines(Row0(row)..Row0(row + 1), true)?
+                    .next()
+                    .map_or(0, |line| line.as_bytes().len());
+                line_lens.insert(row, len);
+                len
+            },
+        };
+
+        Ok((Row0(row), ByteCol(col.min(len))))
+    };
+
+    spans
+        .into_iter()
+        .map(|span| {
+            Ok(HighlightSpan {
+                start: clamp(span.start)?,
+                end: clamp(span.end)?,
+                ..span
+            })
+        })
+        .collect()
+}
+
+/// Returns the span highlighted by an extmark, or `None` if the extmark
+/// wasn't created by a layer.
+fn extmark_span(
+    row: Row0,
+    col: ByteCol,
+    infos: ExtmarkInfos,
+) -> Option<HighlightSpan> {
+    let Some(OneOrMore::One(hl_group)) = infos.hl_group else {
+        return None;
+    };
+
+    Some(HighlightSpan {
+        start: (row, col),
+        end: (infos.end_row?, infos.end_col?),
+        hl_group,
+        priority: infos.priority.unwrap_or(HighlightSpan::DEFAULT_PRIORITY),
+    })
+}
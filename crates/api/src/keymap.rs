@@ -0,0 +1,223 @@
+use std::rc::Rc;
+
+use types::Function;
+
+use crate::Buffer;
+use crate::Result;
+use crate::ToFunction;
+use crate::opts::SetKeymapOpts;
+use crate::types::Mode;
+use crate::utils;
+
+/// A high-level builder for key mappings, built on top of
+/// [`set_keymap`](crate::set_keymap) and
+/// [`Buffer::set_keymap`](crate::Buffer::set_keymap).
+///
+/// Like Lua's `vim.keymap.set()`, mappings are non-recursive by default, and
+/// the keys returned by [`expr`](Keymap::expr) callbacks have their keycodes
+/// replaced unless [`replace_keycodes`](Keymap::replace_keycodes) is set to
+/// `false`.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::api::{Keymap, types::Mode};
+///
+/// Keymap::new(Mode::Insert, "<C-l>")
+///     .expr(|()| String::from("<Right>"))
+///     .desc("Move the cursor right")
+///     .buffer(&nvim_oxi::api::Buffer::current())
+///     .set()?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct Keymap {
+    mode: Mode,
+    lhs: String,
+    rhs: String,
+    callback: Option<Rc<KeymapRhsCallback>>,
+    buffer: Option<Buffer>,
+    desc: Option<String>,
+    noremap: bool,
+    nowait: bool,
+    replace_keycodes: Option<bool>,
+    silent: bool,
+    unique: bool,
+}
+
+/// The callback of a [`Keymap`], shared by all its clones. The reference to
+/// the function is released when the last clone is dropped.
+#[derive(Debug)]
+enum KeymapRhsCallback {
+    Normal(Function<(), ()>),
+    Expr(Function<(), String>),
+}
+
+impl Drop for KeymapRhsCallback {
+    #[inline]
+    fn drop(&mut self) {
+        let lua_ref = match self {
+            Self::Normal(fun) => fun.lua_ref(),
+            Self::Expr(fun) => fun.lua_ref(),
+        };
+        luajit::function::remove(lua_ref);
+    }
+}
+
+impl Keymap {
+    /// Creates a new keymap for the given mode and left-hand side. The
+    /// right-hand side can then be set with one of [`rhs`](Keymap::rhs),
+    /// [`callback`](Keymap::callback) or [`expr`](Keymap::expr).
+    #[inline]
+    pub fn new(mode: Mode, lhs: impl Into<String>) -> Self {
+        Self {
+            mode,
+            lhs: lhs.into(),
+            rhs: String::new(),
+            callback: None,
+            buffer: None,
+            desc: None,
+            noremap: true,
+            nowait: false,
+            replace_keycodes: None,
+            silent: false,
+            unique: false,
+        }
+    }
+
+    /// Makes the keymap local to the given buffer.
+    #[inline]
+    pub fn buffer(mut self, buffer: &Buffer) -> Self {
+        self.buffer = Some(buffer.clone());
+        self
+    }
+
+    /// Sets a function to call when the mapping is executed.
+    #[inline]
+    pub fn callback<F>(mut self, fun: F) -> Self
+    where
+        F: ToFunction<(), ()>,
+    {
+        let fun = utils::function_from_luaref(fun.into_luaref());
+        self.callback = Some(Rc::new(KeymapRhsCallback::Normal(fun)));
+        self
+    }
+
+    /// Sets a description for the keymap.
+    #[inline]
+    pub fn desc(mut self, desc: impl Into<String>) -> Self {
+        self.desc = Some(desc.into());
+        self
+    }
+
+    /// Turns the keymap into an expression mapping: the function is called
+    /// every time the mapping is executed, and the keys it returns are fed to
+    /// Neovim.
+    #[inline]
+    pub fn expr<F>(mut self, fun: F) -> Self
+    where
+        F: ToFunction<(), String>,
+    {
+        let fun = utils::function_from_luaref(fun.into_luaref());
+        self.callback = Some(Rc::new(KeymapRhsCallback::Expr(fun)));
+        self
+    }
+
+    /// For buffer-local mappings, whether Neovim should wait for more
+    /// characters to be typed if there's a global mapping that could also
+    /// match. See `:h map-nowait` for more details.
+    #[inline]
+    pub fn nowait(mut self, nowait: bool) -> Self {
+        self.nowait = nowait;
+        self
+    }
+
+    /// Whether the right-hand side of the mapping should be remappable.
+    /// Defaults to `false`.
+    #[inline]
+    pub fn remap(mut self, remap: bool) -> Self {
+        self.noremap = !remap;
+        self
+    }
+
+    /// Whether to replace the keycodes in the string returned by an
+    /// [`expr`](Keymap::expr) callback. Defaults to `true`.
+    #[inline]
+    pub fn replace_keycodes(mut self, replace_keycodes: bool) -> Self {
+        self.replace_keycodes = Some(replace_keycodes);
+        self
+    }
+
+    /// Sets the right-hand side of the keymap to a sequence of keys.
+    #[inline]
+    pub fn rhs(mut self, rhs: impl Into<String>) -> Self {
+        self.rhs = rhs.into();
+        self.callback = None;
+        self
+    }
+
+    /// Whether the keymap should be silent.
+    #[inline]
+    pub fn silent(mut self, silent: bool) -> Self {
+        self.silent = silent;
+        self
+    }
+
+    /// Whether setting the keymap should fail if another keymap with the
+    /// same left-hand side already exists.
+    #[inline]
+    pub fn unique(mut self, unique: bool) -> Self {
+        self.unique = unique;
+        self
+    }
+
+    /// Deletes the keymap.
+    pub fn del(&self) -> Result<()> {
+        match &self.buffer {
+            Some(buffer) => buffer.clone().del_keymap(self.mode, &self.lhs),
+            None => crate::del_keymap(self.mode, &self.lhs),
+        }
+    }
+
+    /// Sets the keymap.
+    pub fn set(&self) -> Result<()> {
+        let opts = self.opts();
+        match &self.buffer {
+            Some(buffer) => buffer
+                .clone()
+                .set_keymap(self.mode, &self.lhs, &self.rhs, &opts),
+            None => crate::set_keymap(self.mode, &self.lhs, &self.rhs, &opts),
+        }
+    }
+
+    fn opts(&self) -> SetKeymapOpts {
+        let mut builder = SetKeymapOpts::builder();
+
+        builder
+            .noremap(self.noremap)
+            .nowait(self.nowait)
+            .silent(self.silent)
+            .unique(self.unique);
+
+        if let Some(desc) = &self.desc {
+            builder.desc(desc.as_str());
+        }
+
+        match self.callback.as_deref() {
+            // Neovim takes ownership of the callback's reference, so we give
+            // it a new one every time the keymap is set.
+            Some(KeymapRhsCallback::Normal(fun)) => {
+                builder
+                    .callback(utils::clone_function_ref::<_, _, (), ()>(fun));
+            },
+            Some(KeymapRhsCallback::Expr(fun)) => {
+                builder
+                    .callback(utils::clone_function_ref::<_, _, (), ()>(fun))
+                    .expr(true)
+                    .replace_keycodes(self.replace_keycodes.unwrap_or(true));
+            },
+            None => {},
+        }
+
+        builder.build()
+    }
+}
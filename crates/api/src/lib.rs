@@ -13,18 +13,34 @@
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+mod anchor;
 mod autocmd;
+mod buf_attachment;
 mod buffer;
+mod buffer_lines;
+mod buffer_mirror;
 mod command;
+mod decoration_provider;
 mod deprecated;
+pub mod diagnostic;
 mod error;
 mod extmark;
 mod ffi;
+mod float_builder;
+mod highlight;
+mod highlight_layer;
+mod keymap;
+mod line_diff;
+pub mod lsp;
+mod operator;
 mod options;
 pub mod opts;
+mod quickfix;
 pub(crate) mod serde_utils;
 mod tabpage;
+mod text_edit;
 mod trait_utils;
+pub mod treesitter;
 pub mod types;
 pub(crate) mod utils;
 mod vim;
@@ -32,9 +48,14 @@ mod vimscript;
 mod win_config;
 mod window;
 
+pub use anchor::*;
 pub use autocmd::*;
+pub use buf_attachment::*;
 pub use buffer::*;
+pub use buffer_lines::*;
+pub use buffer_mirror::*;
 pub use command::*;
+pub use decoration_provider::*;
 #[allow(
     unused_imports,
     reason = "emits warning if there are no deprecated functions"
@@ -43,8 +64,14 @@ pub use deprecated::*;
 pub use error::Error;
 use error::Result;
 pub use extmark::*;
+pub use float_builder::*;
+pub use highlight::*;
+pub use highlight_layer::*;
+pub use keymap::*;
 pub use luajit::IntoResult;
+pub use operator::*;
 pub use options::*;
+pub use quickfix::*;
 pub use tabpage::*;
 pub use trait_utils::*;
 pub use vim::*;
@@ -0,0 +1,222 @@
+//! A line diff based on Myers' algorithm, used by
+//! [`Buffer::replace_contents_minimal`](crate::Buffer::replace_contents_minimal).
+
+use core::ops::Range;
+
+/// The maximum number of inserted and deleted items the diff looks for. Past
+/// it, finding the minimal diff would take too much time and memory, so the
+/// whole differing region is replaced instead.
+const MAX_EDIT_DISTANCE: isize = 1024;
+
+/// A region in which the old and new sequences differ.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Hunk {
+    /// The replaced items of the old sequence.
+    pub(crate) old: Range<usize>,
+
+    /// The items of the new sequence that replace them.
+    pub(crate) new: Range<usize>,
+}
+
+/// Returns the hunks that turn `old` into `new`, sorted and separated by at
+/// least one common item.
+///
+/// If more than [`MAX_EDIT_DISTANCE`] items have to be inserted or deleted,
+/// a single hunk replacing everything between the common prefix and suffix
+/// is returned.
+pub(crate) fn diff<T: PartialEq>(old: &[T], new: &[T]) -> Vec<Hunk> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let Some(common) = common_items(old_mid, new_mid) else {
+        return vec![Hunk {
+            old: prefix..prefix + old_mid.len(),
+            new: prefix..prefix + new_mid.len(),
+        }];
+    };
+
+    let mut hunks = Vec::new();
+    let mut prev = (0, 0);
+
+    let matches = common.into_iter().chain([(old_mid.len(), new_mid.len())]);
+
+    for (x, y) in matches {
+        if x > prev.0 || y > prev.1 {
+            hunks.push(Hunk {
+                old: prefix + prev.0..prefix + x,
+                new: prefix + prev.1..prefix + y,
+            });
+        }
+        prev = (x + 1, y + 1);
+    }
+
+    hunks
+}
+
+/// Returns the indices `(old_idx, new_idx)` of the items in the longest
+/// common subsequence of the two sequences, sorted, or `None` if the
+/// sequences differ by more than [`MAX_EDIT_DISTANCE`] items.
+fn common_items<T: PartialEq>(
+    old: &[T],
+    new: &[T],
+) -> Option<Vec<(usize, usize)>> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max = (n + m).min(MAX_EDIT_DISTANCE);
+
+    if n + m == 0 {
+        return Some(Vec::new());
+    }
+
+    // `v[k]` is the furthest `x` reached on diagonal `k = x - y`.
+    let idx = |k: isize| (k + max + 1) as usize;
+    let mut v = vec![0isize; 2 * max as usize + 3];
+
+    // Step `d` only reads the diagonals in `-d - 1..=d + 1` of the previous
+    // one, so that's all we keep for walking back.
+    let mut trace = Vec::new();
+    let mut found = false;
+
+    'search: for d in 0..=max {
+        trace.push(v[idx(-d - 1)..=idx(d + 1)].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && v[idx(k - 1)] < v[idx(k + 1)])
+            {
+                v[idx(k + 1)]
+            } else {
+                v[idx(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[idx(k)] = x;
+            if x >= n && y >= m {
+                found = true;
+                break 'search;
+            }
+        }
+    }
+
+    if !found {
+        return None;
+    }
+
+    // Walk back from the end, collecting the diagonal moves.
+    let mut matches = Vec::new();
+    let (mut x, mut y) = (n, m);
+
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let at = |k: isize| v[(k + d + 1) as usize];
+
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+
+        let prev_x = at(prev_k);
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            matches.push((x as usize, y as usize));
+        }
+
+        if d > 0 {
+            x = prev_x;
+            y = prev_y;
+        }
+    }
+
+    matches.reverse();
+    Some(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hunk(old: Range<usize>, new: Range<usize>) -> Hunk {
+        Hunk { old, new }
+    }
+
+    #[test]
+    fn equal() {
+        assert_eq!(diff(&["a", "b"], &["a", "b"]), []);
+        assert_eq!(diff::<&str>(&[], &[]), []);
+    }
+
+    #[test]
+    fn insertion_and_deletion() {
+        assert_eq!(diff(&["a", "c"], &["a", "b", "c"]), [hunk(1..1, 1..2)]);
+        assert_eq!(diff(&["a", "b", "c"], &["a", "c"]), [hunk(1..2, 1..1)]);
+        assert_eq!(diff(&["a"], &["a", "b"]), [hunk(1..1, 1..2)]);
+        assert_eq!(diff(&["a", "b"], &[]), [hunk(0..2, 0..0)]);
+    }
+
+    #[test]
+    fn multiple_hunks() {
+        let old = ["a", "b", "c", "d", "e", "f"];
+        let new = ["a", "x", "c", "d", "f", "g"];
+        assert_eq!(
+            diff(&old, &new),
+            [hunk(1..2, 1..2), hunk(4..5, 4..4), hunk(6..6, 5..6)]
+        );
+    }
+
+    #[test]
+    fn reordered() {
+        let old = ["a", "b", "c"];
+        let new = ["c", "b", "a"];
+        let hunks = diff(&old, &new);
+
+        // Applying the hunks to `old` has to produce `new`.
+        let mut result = Vec::new();
+        let mut last = 0;
+        for hunk in &hunks {
+            result.extend_from_slice(&old[last..hunk.old.start]);
+            result.extend_from_slice(&new[hunk.new.clone()]);
+            last = hunk.old.end;
+        }
+        result.extend_from_slice(&old[last..]);
+        assert_eq!(result, new);
+        assert_eq!(hunks.len(), 2);
+    }
+
+    #[test]
+    fn large_and_completely_different() {
+        let old = (0..100_000).collect::<Vec<_>>();
+        let new = (100_000..250_000).collect::<Vec<_>>();
+        assert_eq!(diff(&old, &new), [hunk(0..100_000, 0..150_000)]);
+    }
+
+    #[test]
+    fn large_with_few_changes() {
+        let old = (0..100_000).collect::<Vec<_>>();
+        let mut new = old.clone();
+        new[10] = 0;
+        new.remove(50_000);
+        new.insert(90_000, 0);
+        assert_eq!(
+            diff(&old, &new),
+            [
+                hunk(10..11, 10..11),
+                hunk(50_000..50_001, 50_000..50_000),
+                hunk(90_001..90_001, 90_000..90_001)
+            ]
+        );
+    }
+}
@@ -0,0 +1,426 @@
+//! Support for language servers running inside the Neovim process.
+//!
+//! A type implementing [`LanguageServer`] can be registered with [`start`],
+//! which calls [`vim.lsp.start()`][1] with a `cmd` function returning an
+//! in-process RPC client instead of spawning an external binary. Requests
+//! and notifications sent by Neovim's LSP client are then forwarded to the
+//! server, which can talk back to the client through the given [`Client`].
+//!
+//! [1]: https://neovim.io/doc/user/lsp.html#vim.lsp.start()
+
+use core::cell::{Cell, RefCell};
+use core::ffi::c_int;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use luajit::Pushable;
+use types::{Dictionary, Function, Object, ObjectKind};
+
+use crate::utils::LuaValue;
+use crate::{Buffer, Error, Result, utils};
+
+/// A language server handling the messages sent by Neovim's LSP client.
+///
+/// Every message is handled on the main thread, in the order it's sent by
+/// the client. This includes the `initialize` and `shutdown` requests, and
+/// the `initialized` and `exit` notifications.
+pub trait LanguageServer: 'static {
+    /// Handles a request, returning either its result or an error.
+    fn on_request(
+        &mut self,
+        client: &Client,
+        method: &str,
+        params: Object,
+    ) -> core::result::Result<Object, ResponseError>;
+
+    /// Handles a notification. The default implementation ignores it.
+    fn on_notification(
+        &mut self,
+        client: &Client,
+        method: &str,
+        params: Object,
+    ) {
+        let _ = (client, method, params);
+    }
+}
+
+/// A handle to the LSP client a [`LanguageServer`] is connected to.
+///
+/// The client's dispatchers are released once the last handle is dropped.
+#[derive(Clone)]
+pub struct Client {
+    closing: Rc<Cell<bool>>,
+    notification: Rc<LuaValue>,
+    on_exit: Rc<LuaValue>,
+}
+
+/// An error returned by [`LanguageServer::on_request`], sent to the client
+/// as a [`ResponseError`][1].
+///
+/// [1]: https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#responseError
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResponseError {
+    /// The error code.
+    pub code: i32,
+
+    /// A short description of the error.
+    pub message: String,
+
+    /// Additional informations about the error.
+    pub data: Option<Object>,
+}
+
+/// The configuration of a language server started with [`start`]. See
+/// [`vim.lsp.ClientConfig`][1] for more infos.
+///
+/// [1]: https://neovim.io/doc/user/lsp.html#vim.lsp.ClientConfig
+#[derive(Clone, Debug)]
+pub struct ServerConfig {
+    name: String,
+    buffer: Option<Buffer>,
+    init_options: Option<Object>,
+    root_dir: Option<String>,
+}
+
+impl Client {
+    /// Whether the client has asked the server to exit.
+    #[inline]
+    pub fn is_closing(&self) -> bool {
+        self.closing.get()
+    }
+
+    /// Sends a notification to the client, e.g.
+    /// `textDocument/publishDiagnostics`. The notification is dispatched by
+    /// the main event-loop.
+    pub fn notify(
+        &self,
+        method: &str,
+        params: impl Into<Object>,
+    ) -> Result<()> {
+        let notification = Rc::clone(&self.notification);
+        let method = method.to_owned();
+        let params = params.into();
+        utils::schedule(move |()| {
+            let _ = notification.call::<_, ()>((method, params), 0);
+        })
+    }
+
+    fn from_dispatchers(dispatchers: Dictionary) -> Result<Self> {
+        let mut notification = None;
+        let mut on_exit = None;
+
+        // Converting the table to a `Dictionary` stored every dispatcher in
+        // the registry, so the ones we don't use are released right away.
+        for (name, obj) in dispatchers {
+            if obj.kind() != ObjectKind::LuaRef {
+                continue;
+            }
+
+            let dispatcher =
+                LuaValue::from_ref(unsafe { obj.as_luaref_unchecked() });
+
+            match name.as_bytes() {
+                b"notification" => notification = Some(dispatcher),
+                b"on_exit" => on_exit = Some(dispatcher),
+                _ => {},
+            }
+        }
+
+        let missing =
+            |name: &str| Error::custom(format!("missing `{name}` dispatcher"));
+
+        Ok(Self {
+            closing: Rc::new(Cell::new(false)),
+            notification: Rc::new(
+                notification.ok_or_else(|| missing("notification"))?,
+            ),
+            on_exit: Rc::new(on_exit.ok_or_else(|| missing("on_exit"))?),
+        })
+    }
+
+    fn exit(&self) -> Result<()> {
+        if self.closing.replace(true) {
+            return Ok(());
+        }
+        let on_exit = Rc::clone(&self.on_exit);
+        utils::schedule(move |()| {
+            let _ = on_exit.call::<_, ()>((0, 0), 0);
+        })
+    }
+}
+
+impl ResponseError {
+    pub const PARSE_ERROR: i32 = -32700;
+    pub const INVALID_REQUEST: i32 = -32600;
+    pub const METHOD_NOT_FOUND: i32 = -32601;
+    pub const INVALID_PARAMS: i32 = -32602;
+    pub const INTERNAL_ERROR: i32 = -32603;
+    pub const REQUEST_FAILED: i32 = -32803;
+
+    /// Creates a new error with the given code and message.
+    #[inline]
+    pub fn new(code: i32, message: impl Into<String>) -> Self {
+        Self { code, message: message.into(), data: None }
+    }
+
+    /// Creates a new error for a request whose method is not supported by
+    /// the server.
+    #[inline]
+    pub fn method_not_found(method: &str) -> Self {
+        Self::new(
+            Self::METHOD_NOT_FOUND,
+            format!("method not found: {method}"),
+        )
+    }
+
+    /// Attaches additional data to the error.
+    #[inline]
+    pub fn data(mut self, data: impl Into<Object>) -> Self {
+        self.data = Some(data.into());
+        self
+    }
+}
+
+impl From<ResponseError> for Object {
+    fn from(err: ResponseError) -> Self {
+        let mut dict = Dictionary::new();
+        dict.insert("code", err.code);
+        dict.insert("message", err.message);
+        if let Some(data) = err.data {
+            dict.insert("data", data);
+        }
+        dict.into()
+    }
+}
+
+impl ServerConfig {
+    /// Creates a new configuration for a server with the given name.
+    #[inline]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            buffer: None,
+            init_options: None,
+            root_dir: None,
+        }
+    }
+
+    /// The buffer to attach the client to. Defaults to the current buffer.
+    #[inline]
+    pub fn buffer(mut self, buffer: &Buffer) -> Self {
+        self.buffer = Some(buffer.clone());
+        self
+    }
+
+    /// The `initializationOptions` sent with the `initialize` request.
+    #[inline]
+    pub fn init_options(mut self, init_options: impl Into<Object>) -> Self {
+        self.init_options = Some(init_options.into());
+        self
+    }
+
+    /// The root directory of the workspace. The client is reused for every
+    /// buffer with the same name and root directory.
+    #[inline]
+    pub fn root_dir(mut self, root_dir: impl Into<String>) -> Self {
+        self.root_dir = Some(root_dir.into());
+        self
+    }
+}
+
+/// Binding to [`vim.lsp.start()`][1].
+///
+/// Starts the given in-process language server, or reuses an existing client
+/// with the same name and root directory, and attaches it to the
+/// configured buffer. Returns the ID of the client.
+///
+/// [1]: https://neovim.io/doc/user/lsp.html#vim.lsp.start()
+pub fn start<S>(config: &ServerConfig, server: S) -> Result<u32>
+where
+    S: LanguageServer,
+{
+    let dispatcher = Rc::new(Dispatcher {
+        server: RefCell::new(server),
+        queue: RefCell::new(VecDeque::new()),
+    });
+
+    let cmd = Function::<(Dictionary, Object), RpcClient>::from_fn(
+        move |(dispatchers, _config)| {
+            let client = Client::from_dispatchers(dispatchers)?;
+            Ok::<_, Error>(rpc_client(Rc::clone(&dispatcher), client))
+        },
+    );
+
+    let cmd_ref = cmd.lua_ref();
+
+    let mut lsp_config = Dictionary::new();
+    lsp_config.insert("name", config.name.as_str());
+    lsp_config.insert("cmd", cmd);
+    lsp_config.insert("init_options", config.init_options.clone());
+    lsp_config.insert("root_dir", config.root_dir.as_deref());
+
+    let mut opts = Dictionary::new();
+    opts.insert("bufnr", config.buffer.clone());
+
+    let res: Result<Option<u32>> =
+        utils::call_vim_fn(&[c"lsp", c"start"], (lsp_config, opts));
+
+    // The client's config now holds the function, so our reference can be
+    // released.
+    luajit::function::remove(cmd_ref);
+
+    res?.ok_or_else(|| Error::custom("couldn't start the language server"))
+}
+
+/// A server shared by all the clients started by the same `cmd` function,
+/// together with the messages waiting to be handled.
+struct Dispatcher<S> {
+    server: RefCell<S>,
+    queue: RefCell<VecDeque<(Client, Message)>>,
+}
+
+/// A message sent by a client to the server.
+enum Message {
+    Request {
+        method: String,
+        params: Object,
+        callback: Function<(Object, Object), ()>,
+    },
+    Notification {
+        method: String,
+        params: Object,
+    },
+}
+
+impl<S: LanguageServer> Dispatcher<S> {
+    /// Queues a message and handles all the queued messages, in order.
+    ///
+    /// If the server is already handling a message (e.g. because one of its
+    /// handlers caused the client to send another message) this only queues
+    /// the message, which is then handled by the outer call once the current
+    /// handler returns. The first error is returned once the queue is empty.
+    fn dispatch(&self, client: &Client, message: Message) -> Result<()> {
+        self.queue.borrow_mut().push_back((client.clone(), message));
+
+        let Ok(mut server) = self.server.try_borrow_mut() else {
+            return Ok(());
+        };
+
+        let mut res = Ok(());
+
+        loop {
+            let Some((client, message)) = self.queue.borrow_mut().pop_front()
+            else {
+                return res;
+            };
+
+            match message {
+                Message::Request { method, params, callback } => {
+                    let (err, result) =
+                        match server.on_request(&client, &method, params) {
+                            Ok(result) => (Object::nil(), result),
+                            Err(err) => (err.into(), Object::nil()),
+                        };
+
+                    let callback_ref = callback.lua_ref();
+
+                    // The client only registers the request after the
+                    // `request` function returns, so the callback has to be
+                    // called later.
+                    let scheduled = utils::schedule(move |()| {
+                        let _ = callback.call((err, result));
+                        callback.remove_from_lua_registry();
+                    });
+
+                    // The closure was dropped without being called.
+                    if let Err(err) = scheduled {
+                        luajit::function::remove(callback_ref);
+                        res = res.and(Err(err));
+                    }
+                },
+                Message::Notification { method, params } => {
+                    server.on_notification(&client, &method, params);
+                },
+            }
+        }
+    }
+}
+
+/// The RPC client returned by the `cmd` function, i.e. a table of functions
+/// forwarding the client's messages to the server.
+struct RpcClient(Dictionary);
+
+impl Pushable for RpcClient {
+    unsafe fn push(
+        self,
+        lstate: *mut luajit::ffi::State,
+    ) -> core::result::Result<c_int, luajit::Error> {
+        let lua_refs = self
+            .0
+            .iter()
+            .filter(|(_, obj)| obj.kind() == ObjectKind::LuaRef)
+            .map(|(_, obj)| obj.as_luaref_unchecked())
+            .collect::<Vec<_>>();
+
+        let num_pushed = self.0.push(lstate)?;
+
+        // The table now holds the functions, so our references can be
+        // released.
+        for lua_ref in lua_refs {
+            luajit::function::remove(lua_ref);
+        }
+
+        Ok(num_pushed)
+    }
+}
+
+/// Creates the RPC client returned by the `cmd` function.
+fn rpc_client<S>(dispatcher: Rc<Dispatcher<S>>, client: Client) -> RpcClient
+where
+    S: LanguageServer,
+{
+    type Callback = Function<(Object, Object), ()>;
+
+    let message_id = Cell::new(0u32);
+
+    let request = {
+        let dispatcher = Rc::clone(&dispatcher);
+        let client = client.clone();
+        Function::<(String, Object, Callback, Object), (bool, u32)>::from_fn(
+            move |(method, params, callback, _notify_reply_callback)| {
+                let id = message_id.get() + 1;
+                message_id.set(id);
+                let message = Message::Request { method, params, callback };
+                dispatcher.dispatch(&client, message)?;
+                Ok::<_, Error>((true, id))
+            },
+        )
+    };
+
+    let notify = {
+        let client = client.clone();
+        Function::<(String, Object), bool>::from_fn(move |(method, params)| {
+            let is_exit = method == "exit";
+            let message = Message::Notification { method, params };
+            dispatcher.dispatch(&client, message)?;
+            if is_exit {
+                client.exit()?;
+            }
+            Ok::<_, Error>(true)
+        })
+    };
+
+    let is_closing = {
+        let client = client.clone();
+        Function::<(), bool>::from_fn(move |()| client.is_closing())
+    };
+
+    let terminate = Function::<(), ()>::from_fn(move |()| client.exit());
+
+    RpcClient(Dictionary::from_iter([
+        ("request", Object::from(request)),
+        ("notify", Object::from(notify)),
+        ("is_closing", Object::from(is_closing)),
+        ("terminate", Object::from(terminate)),
+    ]))
+}
@@ -0,0 +1,132 @@
+use core::cell::Cell;
+use core::ffi::CStr;
+use std::error::Error as StdError;
+use std::ffi::CString;
+
+use luajit::{self as lua, ffi};
+use types::{Function, LuaRef};
+
+use crate::opts::OptionOpts;
+use crate::types::{MotionType, OperatorRange};
+use crate::{Buffer, Error, IntoResult, Result};
+
+/// The global table holding the functions registered with [`operator`] by
+/// every plugin, since the `operatorfunc` option only accepts names of
+/// functions.
+const OPERATORFUNCS: &CStr = c"__nvim_oxi_operatorfuncs";
+
+thread_local! {
+    /// The registry reference to the last function registered by this
+    /// plugin, which also names its entry in [`OPERATORFUNCS`]. References
+    /// are unique for as long as they're held, so plugins can't overwrite
+    /// each other's functions.
+    static OPERATORFUNC_REF: Cell<Option<LuaRef>> = const { Cell::new(None) };
+}
+
+/// Registers a function as the
+/// [`operatorfunc`](https://neovim.io/doc/user/options.html#'operatorfunc')
+/// and returns the `g@` keys that start the operator.
+///
+/// The function is called with the [`MotionType`] and the [`OperatorRange`]
+/// of the text the operator acts on once the user has typed a motion or
+/// text object, and again every time the operation is repeated with `.`.
+///
+/// This is usually called from an [`expr`](crate::Keymap::expr) mapping,
+/// so that the keys returned are fed to Neovim right away.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::api::{self, Keymap, types::Mode};
+///
+/// Keymap::new(Mode::Normal, "gs")
+///     .expr(|()| {
+///         api::operator(|motion_type, range| {
+///             nvim_oxi::print!("{motion_type:?} {range:?}");
+///         })
+///     })
+///     .set()?;
+/// ```
+pub fn operator<F, R>(mut fun: F) -> Result<String>
+where
+    F: FnMut(MotionType, OperatorRange) -> R + 'static,
+    R: IntoResult<()>,
+    R::Error: StdError + 'static,
+{
+    let operatorfunc = Function::<MotionType, ()>::from_fn_mut(
+        move |motion_type| -> Result<()> {
+            let range = operator_range()?;
+            fun(motion_type, range).into_result().map_err(Error::custom)
+        },
+    );
+
+    let lua_ref = operatorfunc.lua_ref();
+    let previous = OPERATORFUNC_REF.replace(Some(lua_ref));
+
+    unsafe {
+        lua::with_state(|lstate| {
+            ffi::lua_getfield(
+                lstate,
+                ffi::LUA_GLOBALSINDEX,
+                OPERATORFUNCS.as_ptr(),
+            );
+
+            if ffi::lua_type(lstate, -1) != ffi::LUA_TTABLE {
+                ffi::lua_pop(lstate, 1);
+                ffi::lua_createtable(lstate, 0, 1);
+                ffi::lua_pushstring(lstate, OPERATORFUNCS.as_ptr());
+                ffi::lua_pushvalue(lstate, -2);
+                ffi::lua_rawset(lstate, ffi::LUA_GLOBALSINDEX);
+            }
+
+            if let Some(previous) = previous {
+                ffi::lua_pushstring(
+                    lstate,
+                    operatorfunc_key(previous).as_ptr(),
+                );
+                ffi::lua_pushnil(lstate);
+                ffi::lua_rawset(lstate, -3);
+            }
+
+            ffi::lua_pushstring(lstate, operatorfunc_key(lua_ref).as_ptr());
+            ffi::lua_rawgeti(lstate, ffi::LUA_REGISTRYINDEX, lua_ref);
+            ffi::lua_rawset(lstate, -3);
+
+            ffi::lua_pop(lstate, 1);
+        })
+    };
+
+    // The previous function was removed from the table, so its name can be
+    // reused once its reference is released.
+    if let Some(previous) = previous {
+        lua::function::remove(previous);
+    }
+
+    let name = format!(
+        "v:lua.{}.{}",
+        OPERATORFUNCS.to_string_lossy(),
+        operatorfunc_key(lua_ref).to_string_lossy()
+    );
+
+    crate::set_option_value("operatorfunc", name, &OptionOpts::default())?;
+
+    Ok("g@".to_owned())
+}
+
+/// Returns the key of the function with the given registry reference in
+/// [`OPERATORFUNCS`].
+fn operatorfunc_key(lua_ref: LuaRef) -> CString {
+    CString::new(format!("f{lua_ref}")).expect("no null bytes")
+}
+
+/// Returns the range delimited by the `'[` and `']` marks in the current
+/// buffer.
+fn operator_range() -> Result<OperatorRange> {
+    let buffer = Buffer::current();
+    let mark = |name| {
+        buffer.get_mark(name)?.ok_or_else(|| {
+            Error::custom(format!("the '{name} mark is not set"))
+        })
+    };
+    Ok(OperatorRange { start: mark('[')?, end: mark(']')? })
+}
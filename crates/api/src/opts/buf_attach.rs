@@ -1,5 +1,13 @@
+use core::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use types::{Function, LuaRef, Object, ObjectKind};
+
 use crate::Buffer;
 use crate::ToFunction;
+use crate::types::BufEvent;
+use crate::utils::{LuaArgs, LuaValue};
 
 /// Arguments passed to the callback registered to
 /// [`on_lines`](BufAttachOptsBuilder::on_lines).
@@ -148,3 +156,135 @@ pub struct BufAttachOpts {
     #[builder(argtype = "bool")]
     preview: types::Boolean,
 }
+
+impl BufAttachOptsBuilder {
+    /// Registers a single callback receiving every event as a typed
+    /// [`BufEvent`], replacing the callbacks set with
+    /// [`on_lines`](Self::on_lines), [`on_bytes`](Self::on_bytes),
+    /// [`on_changedtick`](Self::on_changedtick),
+    /// [`on_detach`](Self::on_detach) and [`on_reload`](Self::on_reload).
+    ///
+    /// Events triggered while the callback is running (e.g. because it edits
+    /// the buffer) are queued, and passed to the callback once it returns.
+    pub fn on_event<F>(&mut self, fun: F) -> &mut Self
+    where
+        F: FnMut(BufEvent) -> ShouldDetach + 'static,
+    {
+        let queue = Rc::new(EventQueue {
+            fun: RefCell::new(fun),
+            pending: RefCell::new(VecDeque::new()),
+        });
+
+        let dispatch = move |event: BufEvent| queue.dispatch(event);
+
+        let on_lines = dispatch.clone();
+        let on_bytes = dispatch.clone();
+        let on_changedtick = dispatch.clone();
+        let on_detach = dispatch.clone();
+        let on_reload = dispatch;
+
+        self.on_lines(move |args: OnLinesArgs| on_lines(args.into()))
+            .on_bytes(move |args: OnBytesArgs| on_bytes(args.into()))
+            .on_changedtick(move |args: OnChangedtickArgs| {
+                on_changedtick(args.into())
+            })
+            .on_detach(move |args| on_detach(BufEvent::detach(args)))
+            .on_reload(move |args| on_reload(BufEvent::reload(args)))
+    }
+}
+
+/// The events waiting to be passed to an [`on_event`] callback.
+///
+/// [`on_event`]: BufAttachOptsBuilder::on_event
+struct EventQueue<F> {
+    fun: RefCell<F>,
+    pending: RefCell<VecDeque<BufEvent>>,
+}
+
+impl<F: FnMut(BufEvent) -> ShouldDetach> EventQueue<F> {
+    /// Queues the event and passes all the queued events to the callback,
+    /// unless it's already running, in which case the event is passed by the
+    /// outer call once the callback returns. The outer call then detaches if
+    /// the callback asked to for any of the events.
+    fn dispatch(&self, event: BufEvent) -> ShouldDetach {
+        self.pending.borrow_mut().push_back(event);
+
+        let Ok(mut fun) = self.fun.try_borrow_mut() else {
+            return false;
+        };
+
+        let mut should_detach = false;
+
+        loop {
+            let Some(event) = self.pending.borrow_mut().pop_front() else {
+                return should_detach;
+            };
+            should_detach |= fun(event);
+        }
+    }
+}
+
+impl BufAttachOpts {
+    /// Returns a copy of the options whose callbacks detach from the buffer
+    /// the next time they're called after `detached` is set. The flag is
+    /// also set when the callbacks are detached by Neovim.
+    ///
+    /// Every call creates new callbacks, since each attachment has its own
+    /// flag. They hold their own references to the original callbacks, which
+    /// are released once Neovim releases them.
+    pub(crate) fn detachable(&self, detached: &Rc<Cell<bool>>) -> Self {
+        let wrap = |lua_ref, is_on_detach| {
+            wrap_callback(lua_ref, Rc::clone(detached), is_on_detach)
+        };
+
+        Self {
+            on_lines: wrap(self.on_lines, false),
+            on_bytes: wrap(self.on_bytes, false),
+            on_changedtick: wrap(self.on_changedtick, false),
+            on_detach: wrap(self.on_detach, true),
+            on_reload: wrap(self.on_reload, false),
+            ..self.clone()
+        }
+    }
+}
+
+fn wrap_callback(
+    lua_ref: LuaRef,
+    detached: Rc<Cell<bool>>,
+    is_on_detach: bool,
+) -> LuaRef {
+    // Unset callbacks have a zero ref, which is never returned by
+    // `luaL_ref`. `on_detach` is wrapped even when unset, since it's the only
+    // way to know when Neovim detaches the callbacks.
+    if lua_ref == 0 && !is_on_detach {
+        return lua_ref;
+    }
+
+    let fun = (lua_ref != 0).then(|| LuaValue::clone_ref(lua_ref));
+
+    let wrapper = Function::<LuaArgs, Object>::from_fn(move |args| {
+        if is_on_detach {
+            detached.set(true);
+        } else if detached.get() {
+            return Ok(true.into());
+        }
+
+        let Some(fun) = &fun else {
+            return Ok(Object::nil());
+        };
+
+        let ret = fun.call::<_, Object>(args, 1)?;
+
+        // Neovim detaches the callbacks when one of them returns `true`,
+        // without calling `on_detach`.
+        if ret.kind() == ObjectKind::Boolean
+            && unsafe { ret.as_boolean_unchecked() }
+        {
+            detached.set(true);
+        }
+
+        Ok::<_, crate::Error>(ret)
+    });
+
+    wrapper.lua_ref()
+}
@@ -1,12 +1,14 @@
 use types::conversion::ToObject;
 
 use crate::Buffer;
+use crate::ToFunction;
 use crate::types::{
     CommandAddr,
     CommandArgs,
     CommandComplete,
     CommandNArgs,
     CommandRange,
+    PreviewMode,
 };
 
 /// Options passed to [`create_user_command`](crate::create_user_command) and
@@ -57,10 +59,19 @@ pub struct CreateCommandOpts {
     #[builder(argtype = "CommandNArgs", inline = "{0}.to_object().unwrap()")]
     nargs: types::Object,
 
+    /// A function called to show a preview of the command's effects while
+    /// it's being typed (see `:h :command-preview`).
+    ///
+    /// The function receives the command's arguments, the id of the
+    /// namespace in which to place the preview highlights and, if
+    /// [`inccommand`](https://neovim.io/doc/user/options.html#'inccommand')
+    /// is set to `split`, the preview [`Buffer`]. It can freely modify the
+    /// current buffer and the preview buffer (e.g. by setting lines or
+    /// extmarks): all the changes are reverted once the preview ends.
     #[builder(
-        generics = r#"F: Into<types::Function<(CommandArgs, Option<u32>, Option<Buffer>), u8>>"#,
+        generics = r#"F: ToFunction<(CommandArgs, u32, Option<Buffer>), PreviewMode>"#,
         argtype = "F",
-        inline = "{0}.into().into()"
+        inline = "types::Object::from_luaref({0}.into_luaref())"
     )]
     preview: types::Object,
 
@@ -38,6 +38,19 @@ pub type OnLineArgs = (
     usize,  // row
 );
 
+/// Arguments passed to the function registered to
+/// [`on_range`](DecorationProviderOptsBuilder::on_range).
+#[cfg(feature = "neovim-0-12")] // On 0.12 and Nightly.
+pub type OnRangeArgs = (
+    String, // the string literal "range"
+    Window, // window
+    Buffer, // buffer
+    usize,  // start_row
+    usize,  // start_col
+    usize,  // end_row
+    usize,  // end_col
+);
+
 /// Arguments passed to the function registered to
 /// [`on_end`](DecorationProviderOptsBuilder::on_end).
 pub type OnEndArgs = (
@@ -53,6 +66,10 @@ pub type DontSkipRedrawCycle = bool;
 /// that window.
 pub type DontSkipOnLines = bool;
 
+/// The `on_range` callback can return `false` to skip the range.
+#[cfg(feature = "neovim-0-12")] // On 0.12 and Nightly.
+pub type DontSkipRange = bool;
+
 /// Options passed to
 /// [`set_decoration_provider()`](crate::set_decoration_provider).
 #[derive(Clone, Debug, Default, macros::OptsBuilder)]
@@ -89,6 +106,14 @@ pub struct DecorationProviderOpts {
     )]
     on_line: LuaRef,
 
+    #[cfg(feature = "neovim-0-12")] // On 0.12 and Nightly.
+    #[builder(
+        generics = "F: ToFunction<OnRangeArgs, DontSkipRange>",
+        argtype = "F",
+        inline = "{0}.into_luaref()"
+    )]
+    on_range: LuaRef,
+
     #[builder(
         generics = "F: ToFunction<OnEndArgs, ()>",
         argtype = "F",
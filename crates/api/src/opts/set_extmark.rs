@@ -2,7 +2,16 @@ use types::{Array, Integer};
 
 use crate::SetExtmarkHlGroup;
 use crate::types::VirtLinesOverflow;
-use crate::types::{ExtmarkHlMode, ExtmarkVirtTextPosition};
+use crate::types::{
+    ByteCol,
+    ExtmarkHlMode,
+    ExtmarkInfos,
+    ExtmarkVirtTextChunk,
+    ExtmarkVirtTextPosition,
+    OneOrMore,
+    Row0,
+    StringOrInt,
+};
 
 /// Options passed to [`Buffer::set_extmark()`](crate::Buffer::set_extmark).
 #[derive(Clone, Debug, Default, macros::OptsBuilder)]
@@ -19,11 +28,11 @@ pub struct SetExtmarkOpts {
     end_line: types::Integer,
 
     /// Ending line of the mark. 0-indexed and inclusive.
-    #[builder(argtype = "usize", inline = "{0} as types::Integer")]
+    #[builder(argtype = "Row0", inline = "{0}.0 as types::Integer")]
     end_row: types::Integer,
 
-    /// Ending line of the mark. 0-indexed and exclusive.
-    #[builder(argtype = "usize", inline = "{0} as types::Integer")]
+    /// Ending column of the mark. 0-indexed and exclusive.
+    #[builder(argtype = "ByteCol", inline = "{0}.0 as types::Integer")]
     end_col: types::Integer,
 
     /// Name of the highlight group used to highlight this mark.
@@ -47,7 +56,9 @@ pub struct SetExtmarkOpts {
     )]
     virt_text: types::Array,
 
-    /// Position of the virtual text.
+    /// Position of the virtual text. Setting
+    /// [`virt_text_win_col`](SetExtmarkOptsBuilder::virt_text_win_col)
+    /// overrides it.
     #[builder(
         argtype = "ExtmarkVirtTextPosition",
         inline = "types::String::from({0})"
@@ -215,6 +226,153 @@ pub struct SetExtmarkOpts {
     _subpriority: types::Integer,
 }
 
+/// Creates the options that recreate an extmark with the given infos, which
+/// can be used to restore a decoration after the text it was on has been
+/// replaced.
+///
+/// The infos must have been returned with the `hl_name` option left to its
+/// default value, because a highlight group given by name is resolved to an
+/// id when the options are built.
+impl From<&ExtmarkInfos> for SetExtmarkOpts {
+    fn from(infos: &ExtmarkInfos) -> Self {
+        let mut builder = SetExtmarkOptsBuilder::default();
+
+        macro_rules! set_hl_group {
+            ($field:ident) => {
+                match &infos.$field {
+                    Some(StringOrInt::String(name)) => {
+                        builder.$field(name.as_str());
+                    },
+                    Some(StringOrInt::Int(id)) => {
+                        builder.$field(*id);
+                    },
+                    None => {},
+                }
+            };
+        }
+
+        if let Some(end_row) = infos.end_row {
+            builder.end_row(end_row);
+        }
+
+        if let Some(end_col) = infos.end_col {
+            builder.end_col(end_col);
+        }
+
+        builder.right_gravity(infos.right_gravity);
+
+        if let Some(end_right_gravity) = infos.end_right_gravity {
+            builder.end_right_gravity(end_right_gravity);
+        }
+
+        if let Some(priority) = infos.priority {
+            builder.priority(priority);
+        }
+
+        match &infos.hl_group {
+            Some(OneOrMore::One(hl_group)) => {
+                builder.hl_group(hl_group.as_str());
+            },
+            Some(OneOrMore::List(hl_groups)) => {
+                builder.hl_group(hl_groups.clone());
+            },
+            None => {},
+        }
+
+        if let Some(hl_eol) = infos.hl_eol {
+            builder.hl_eol(hl_eol);
+        }
+
+        if let Some(hl_mode) = infos.hl_mode {
+            builder.hl_mode(hl_mode);
+        }
+
+        if !infos.virt_text.is_empty() {
+            builder.virt_text(infos.virt_text.iter().map(chunk_to_tuple));
+        }
+
+        if let Some(virt_text_pos) = infos.virt_text_pos {
+            builder.virt_text_pos(virt_text_pos);
+        }
+
+        if let Some(Ok(win_col)) = infos.virt_text_win_col.map(u32::try_from) {
+            builder.virt_text_win_col(win_col);
+        }
+
+        if let Some(virt_text_hide) = infos.virt_text_hide {
+            builder.virt_text_hide(virt_text_hide);
+        }
+
+        if let Some(repeat_linebreak) = infos.virt_text_repeat_linebreak {
+            builder.virt_text_repeat_linebreak(repeat_linebreak);
+        }
+
+        if let Some(virt_lines) = &infos.virt_lines {
+            builder.virt_lines(
+                virt_lines
+                    .iter()
+                    .map(|chunks| chunks.iter().map(chunk_to_tuple)),
+            );
+        }
+
+        if let Some(virt_lines_above) = infos.virt_lines_above {
+            builder.virt_lines_above(virt_lines_above);
+        }
+
+        if let Some(virt_lines_leftcol) = infos.virt_lines_leftcol {
+            builder.virt_lines_leftcol(virt_lines_leftcol);
+        }
+
+        if let Some(virt_lines_overflow) = infos.virt_lines_overflow {
+            builder.virt_lines_overflow(virt_lines_overflow);
+        }
+
+        if let Some(sign_text) = &infos.sign_text {
+            builder.sign_text(sign_text);
+        }
+
+        set_hl_group!(sign_hl_group);
+        set_hl_group!(number_hl_group);
+        set_hl_group!(line_hl_group);
+        set_hl_group!(cursorline_hl_group);
+
+        if let Some(conceal) = &infos.conceal {
+            builder.conceal(conceal.chars().next());
+        }
+
+        if infos.conceal_lines {
+            builder.conceal_lines(());
+        }
+
+        if let Some(spell) = infos.spell {
+            builder.spell(spell);
+        }
+
+        if let Some(ui_watched) = infos.ui_watched {
+            builder.ui_watched(ui_watched);
+        }
+
+        if let Some(undo_restore) = infos.undo_restore {
+            builder.undo_restore(undo_restore);
+        }
+
+        if let Some(invalidate) = infos.invalidate {
+            builder.invalidate(invalidate);
+        }
+
+        if let Some(url) = &infos.url {
+            builder.url(url);
+        }
+
+        builder.build()
+    }
+}
+
+#[inline]
+fn chunk_to_tuple(chunk: &ExtmarkVirtTextChunk) -> (&str, Vec<StringOrInt>) {
+    (chunk.text.as_str(), chunk.hl_groups.clone())
+}
+
 #[inline]
 fn set_virt_lines<Text, Hl, Chunks, Lines>(
     field: &mut Array,
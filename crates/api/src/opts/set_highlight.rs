@@ -1,6 +1,8 @@
 use types::Object;
 use types::{Boolean, Integer, String as NvimString};
 
+use crate::types::Highlight;
+
 // https://github.com/neovim/neovim/blob/v0.11.3/src/nvim/api/keysets_defs.h#L164-L196
 #[derive(Clone, Debug, Default, PartialEq, macros::OptsBuilder)]
 #[repr(C)]
@@ -65,10 +67,22 @@ pub struct SetHighlightOpts {
     #[builder(skip)]
     bg: Object,
 
-    #[builder(argtype = "&str", inline = "types::String::from({0}).into()")]
+    /// The cterm foreground color, either a color name or an index in the
+    /// 256-color palette.
+    #[builder(
+        generics = "C: crate::StringOrInt",
+        argtype = "C",
+        inline = "{0}.to_object()"
+    )]
     ctermfg: Object,
 
-    #[builder(argtype = "&str", inline = "types::String::from({0}).into()")]
+    /// The cterm background color, either a color name or an index in the
+    /// 256-color palette.
+    #[builder(
+        generics = "C: crate::StringOrInt",
+        argtype = "C",
+        inline = "{0}.to_object()"
+    )]
     ctermbg: Object,
 
     #[builder(argtype = "&str", inline = "types::String::from({0}).into()")]
@@ -105,3 +119,91 @@ pub struct SetHighlightOpts {
     #[builder(skip)]
     url: NvimString,
 }
+
+impl From<&Highlight> for SetHighlightOpts {
+    fn from(hl: &Highlight) -> Self {
+        let mut builder = SetHighlightOptsBuilder::default();
+
+        if let Some(link) = &hl.link {
+            builder.link(link.as_str());
+        }
+
+        if let Some(fg) = hl.fg {
+            builder.foreground(&fg.to_string());
+        }
+
+        if let Some(bg) = hl.bg {
+            builder.background(&bg.to_string());
+        }
+
+        if let Some(sp) = hl.sp {
+            builder.special(&sp.to_string());
+        }
+
+        if let Some(ctermfg) = hl.ctermfg {
+            builder.ctermfg(ctermfg);
+        }
+
+        if let Some(ctermbg) = hl.ctermbg {
+            builder.ctermbg(ctermbg);
+        }
+
+        if let Some(blend) = hl.blend {
+            builder.blend(blend);
+        }
+
+        if hl.bold {
+            builder.bold(true);
+        }
+
+        if hl.standout {
+            builder.standout(true);
+        }
+
+        if hl.strikethrough {
+            builder.strikethrough(true);
+        }
+
+        if hl.underline {
+            builder.underline(true);
+        }
+
+        if hl.undercurl {
+            builder.undercurl(true);
+        }
+
+        if hl.underdouble {
+            builder.underdouble(true);
+        }
+
+        if hl.underdotted {
+            builder.underdotted(true);
+        }
+
+        if hl.underdashed {
+            builder.underdashed(true);
+        }
+
+        if hl.italic {
+            builder.italic(true);
+        }
+
+        if hl.reverse {
+            builder.reverse(true);
+        }
+
+        if hl.altfont {
+            builder.altfont(true);
+        }
+
+        if hl.nocombine {
+            builder.nocombine(true);
+        }
+
+        if hl.default {
+            builder.builder(true);
+        }
+
+        builder.build()
+    }
+}
@@ -0,0 +1,172 @@
+use types::{Array, Dictionary, Integer, Object};
+
+use crate::types::{QfItem, QfListInfos};
+use crate::{Error, Result, Window};
+
+/// A handle to the quickfix list stack. See `:h quickfix` for more infos.
+///
+/// Lists are identified by the unique ID returned by
+/// [`set`](Quickfix::set). Every method taking an `id` also accepts `0` to
+/// refer to the current list.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::api::{Buffer, Quickfix};
+/// use nvim_oxi::api::types::{QfItem, QfItemKind, Row1};
+///
+/// let id = Quickfix.set([
+///     QfItem::buffer(Buffer::current(), Row1::FIRST, 1)
+///         .kind(QfItemKind::Error)
+///         .text("unexpected token"),
+/// ])?;
+///
+/// Quickfix.set_title(id, "Build errors")?;
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Quickfix;
+
+/// A handle to the location list stack of a window. Its methods behave like
+/// the ones of [`Quickfix`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct LocList(pub Window);
+
+macro_rules! qf_methods {
+    () => {
+        /// Creates a new list after the current one, makes it the current
+        /// list and returns its ID.
+        pub fn set<I>(&self, items: I) -> Result<u32>
+        where
+            I: IntoIterator<Item = QfItem>,
+        {
+            let mut what = Dictionary::new();
+            what.insert("items", items_to_array(items));
+            self.set_list(" ", what)?;
+            self.current_id()
+        }
+
+        /// Appends the items to the list with the given ID.
+        pub fn append<I>(&self, id: u32, items: I) -> Result<()>
+        where
+            I: IntoIterator<Item = QfItem>,
+        {
+            self.set_items(id, "a", items)
+        }
+
+        /// Replaces all the items of the list with the given ID.
+        pub fn replace<I>(&self, id: u32, items: I) -> Result<()>
+        where
+            I: IntoIterator<Item = QfItem>,
+        {
+            self.set_items(id, "r", items)
+        }
+
+        /// Returns the ID of the current list, or `0` if the stack is empty.
+        pub fn current_id(&self) -> Result<u32> {
+            let what = Dictionary::from_iter([("id", 0)]);
+            self.get_list(what).map(|infos| infos.id)
+        }
+
+        /// Returns all the informations about the list with the given ID.
+        pub fn get(&self, id: u32) -> Result<QfListInfos> {
+            let what = Dictionary::from_iter([("id", id), ("all", 1)]);
+            let infos = self.get_list(what)?;
+            if infos.id == 0 {
+                return Err(Error::custom(format!("no list with ID {id}")));
+            }
+            Ok(infos)
+        }
+
+        /// Sets the title of the list with the given ID.
+        pub fn set_title(&self, id: u32, title: &str) -> Result<()> {
+            let mut what = Dictionary::new();
+            what.insert("id", id);
+            what.insert("title", title);
+            self.set_list("a", what)
+        }
+
+        /// Attaches an arbitrary value to the list with the given ID, which
+        /// can later be retrieved from [`QfListInfos::context`].
+        pub fn set_context<C>(&self, id: u32, context: C) -> Result<()>
+        where
+            C: Into<Object>,
+        {
+            let mut what = Dictionary::new();
+            what.insert("id", id);
+            what.insert("context", context);
+            self.set_list("a", what)
+        }
+
+        /// Removes all the lists from the stack.
+        pub fn clear(&self) -> Result<()> {
+            self.set_list("f", Dictionary::new())
+        }
+
+        fn set_items<I>(&self, id: u32, action: &str, items: I) -> Result<()>
+        where
+            I: IntoIterator<Item = QfItem>,
+        {
+            let mut what = Dictionary::new();
+            what.insert("id", id);
+            what.insert("items", items_to_array(items));
+            self.set_list(action, what)
+        }
+    };
+}
+
+impl Quickfix {
+    qf_methods!();
+
+    fn get_list(&self, what: Dictionary) -> Result<QfListInfos> {
+        crate::call_function("getqflist", (what,))
+    }
+
+    fn set_list(&self, action: &str, what: Dictionary) -> Result<()> {
+        let ret =
+            crate::call_function("setqflist", (Array::new(), action, what))?;
+        check_set_result(ret)
+    }
+}
+
+impl LocList {
+    qf_methods!();
+
+    /// Returns the window the location list belongs to.
+    #[inline]
+    pub fn window(&self) -> &Window {
+        &self.0
+    }
+
+    fn get_list(&self, what: Dictionary) -> Result<QfListInfos> {
+        crate::call_function("getloclist", (&self.0, what))
+    }
+
+    fn set_list(&self, action: &str, what: Dictionary) -> Result<()> {
+        let ret = crate::call_function(
+            "setloclist",
+            (&self.0, Array::new(), action, what),
+        )?;
+        check_set_result(ret)
+    }
+}
+
+impl From<Window> for LocList {
+    #[inline]
+    fn from(window: Window) -> Self {
+        Self(window)
+    }
+}
+
+fn items_to_array<I>(items: I) -> Array
+where
+    I: IntoIterator<Item = QfItem>,
+{
+    items.into_iter().map(Object::from).collect()
+}
+
+fn check_set_result(ret: Integer) -> Result<()> {
+    match ret {
+        0 => Ok(()),
+        _ => Err(Error::custom("couldn't update the list")),
+    }
+}
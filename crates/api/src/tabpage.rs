@@ -5,16 +5,17 @@ use luajit::{self as lua, Poppable, Pushable};
 use serde::{Deserialize, Serialize};
 use types::{
     self as nvim,
+    Array,
     Object,
     TabHandle,
     conversion::{self, FromObject, ToObject},
 };
 
-use crate::Result;
 use crate::SuperIterator;
-use crate::Window;
 use crate::choose;
 use crate::ffi::tabpage::*;
+use crate::types::{Layout, SplitDirection, WindowConfig};
+use crate::{Error, Result, Window};
 
 /// A wrapper around a Neovim tab handle.
 #[derive(Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
@@ -159,6 +160,50 @@ impl TabPage {
         unsafe { nvim_tabpage_is_valid(self.0) }
     }
 
+    /// Returns the layout of the split windows in the tabpage, like
+    /// [`winlayout()`][1].
+    ///
+    /// [1]: https://neovim.io/doc/user/builtin.html#winlayout()
+    pub fn layout(&self) -> Result<Layout> {
+        let number = self.get_number()?;
+        let layout = crate::call_function("winlayout", (number,))?;
+        layout_from_object(layout)
+    }
+
+    /// Replaces the split windows in the tabpage with new ones arranged like
+    /// in the given layout, showing the same buffers and with the same sizes.
+    /// Floating windows are left untouched.
+    ///
+    /// The windows in `layout` don't have to be valid, e.g. it can come from
+    /// a tabpage that was closed since, but its buffers do. Returns the new
+    /// layout, which holds the windows that were created.
+    ///
+    /// The layout is validated before closing any window, so an invalid one
+    /// leaves the tabpage as it was. The windows are closed like with
+    /// `:close!`, so buffers with unsaved changes are hidden even if
+    /// `'hidden'` is not set.
+    pub fn apply_layout(&mut self, layout: &Layout) -> Result<Layout> {
+        validate_layout(layout)?;
+
+        let current = self.layout()?;
+        let mut windows = current.windows().cloned();
+        let root = windows.next().expect("tabpages have at least one window");
+
+        for window in windows {
+            window.close(true)?;
+        }
+
+        let layout = build_layout(layout, root)?;
+
+        // Resizing a window can change the size of the ones resized before
+        // it, so this is done twice like in `winrestcmd()`.
+        for _ in 0..2 {
+            resize_layout(&layout)?;
+        }
+
+        Ok(layout)
+    }
+
     /// Binding to [`nvim_tabpage_list_wins()`][1].
     ///
     /// Gets the windows in a tabpage.
@@ -210,3 +255,143 @@ impl TabPage {
         choose!(err, ())
     }
 }
+
+/// Converts the output of `winlayout()` into a [`Layout`].
+fn layout_from_object(obj: Object) -> Result<Layout> {
+    let mut items = Array::from_object(obj)?.into_iter();
+
+    let (Some(kind), Some(content)) = (items.next(), items.next()) else {
+        return Err(Error::custom("invalid output of winlayout()"));
+    };
+
+    let children = |content| -> Result<Vec<Layout>> {
+        Array::from_object(content)?
+            .into_iter()
+            .map(layout_from_object)
+            .collect()
+    };
+
+    match nvim::String::from_object(kind)?.as_bytes() {
+        b"row" => Ok(Layout::Row(children(content)?)),
+        b"col" => Ok(Layout::Col(children(content)?)),
+        b"leaf" => {
+            let window = Window::from_object(content)?;
+            Ok(Layout::Leaf {
+                buffer: window.get_buf()?,
+                width: window.get_width()?,
+                height: window.get_height()?,
+                window,
+            })
+        },
+        other => Err(Error::custom(format!(
+            "unknown winlayout() node {:?}",
+            String::from_utf8_lossy(other)
+        ))),
+    }
+}
+
+/// Checks that every node of the layout has windows, that no node is nested
+/// in one with the same direction, and that all its buffers are valid.
+fn validate_layout(layout: &Layout) -> Result<()> {
+    match layout {
+        Layout::Row(children) | Layout::Col(children) => {
+            if children.is_empty() {
+                return Err(Error::custom("layout has no windows"));
+            }
+
+            // Neovim merges nested splits with the same direction, so the
+            // resulting layout wouldn't match the given one.
+            let is_nested = children.iter().any(|child| {
+                matches!(
+                    (layout, child),
+                    (Layout::Row(_), Layout::Row(_))
+                        | (Layout::Col(_), Layout::Col(_))
+                )
+            });
+
+            if is_nested {
+                return Err(Error::custom(
+                    "layout nests splits with the same direction",
+                ));
+            }
+
+            children.iter().try_for_each(validate_layout)
+        },
+        Layout::Leaf { buffer, .. } => {
+            if buffer.is_valid() {
+                Ok(())
+            } else {
+                Err(Error::custom(format!(
+                    "layout shows invalid buffer {}",
+                    buffer.handle()
+                )))
+            }
+        },
+    }
+}
+
+/// Arranges the given window and the ones split from it like in `layout`,
+/// returning the resulting layout.
+fn build_layout(layout: &Layout, mut window: Window) -> Result<Layout> {
+    let (children, direction) = match layout {
+        Layout::Row(children) => (children, SplitDirection::Right),
+        Layout::Col(children) => (children, SplitDirection::Below),
+        Layout::Leaf { buffer, width, height, .. } => {
+            window.set_buf(buffer)?;
+            return Ok(Layout::Leaf {
+                window,
+                buffer: buffer.clone(),
+                width: *width,
+                height: *height,
+            });
+        },
+    };
+
+    // All the splits are created before recursing, so that they split the
+    // window of this level instead of one of its descendants.
+    let mut previous = window.clone();
+
+    let splits = children[1..]
+        .iter()
+        .map(|child| {
+            let buffer = child
+                .first_buffer()
+                .ok_or_else(|| Error::custom("layout has no windows"))?;
+
+            let config = WindowConfig::builder()
+                .split(direction)
+                .win(previous.clone())
+                .build();
+
+            previous = crate::open_win(buffer, false, &config)?;
+            Ok(previous.clone())
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let windows = core::iter::once(window).chain(splits);
+
+    let children = children
+        .iter()
+        .zip(windows)
+        .map(|(child, window)| build_layout(child, window))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(match layout {
+        Layout::Row(_) => Layout::Row(children),
+        _ => Layout::Col(children),
+    })
+}
+
+/// Sets the sizes of the windows in the layout.
+fn resize_layout(layout: &Layout) -> Result<()> {
+    match layout {
+        Layout::Row(children) | Layout::Col(children) => {
+            children.iter().try_for_each(resize_layout)
+        },
+        Layout::Leaf { window, width, height, .. } => {
+            let mut window = window.clone();
+            window.set_width(*width)?;
+            window.set_height(*height)
+        },
+    }
+}
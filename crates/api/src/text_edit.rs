@@ -0,0 +1,403 @@
+use core::ops::Range;
+
+use crate::line_diff::{self, Hunk};
+use crate::opts::GetTextOpts;
+use crate::types::{ByteCol, Row0, TextEdit};
+use crate::{Buffer, Error, Result};
+
+impl Buffer {
+    /// Applies a batch of edits to the buffer, as a single undo step.
+    ///
+    /// The ranges of the edits refer to the buffer's content before any of
+    /// them is applied, and must not overlap. Edits inserting text at the
+    /// same position are applied in the order they're given. If an edit
+    /// fails, the ones already applied are reverted.
+    ///
+    /// Returns the edits that revert the change, which can themselves be
+    /// passed to this method. Fails with [`Error::LineFromUtf8`] if a
+    /// replaced line is not valid UTF-8.
+    pub fn apply_edits(
+        &mut self,
+        mut edits: Vec<TextEdit>,
+    ) -> Result<Vec<TextEdit>> {
+        edits.sort_by_key(|edit| (edit.start, edit.end));
+        validate(&edits)?;
+
+        // Read the replaced text before changing anything, which also checks
+        // that the ranges are inside the buffer.
+        let old_texts = edits
+            .iter()
+            .map(|edit| self.get_range_text(edit.start, edit.end))
+            .collect::<Result<Vec<_>>>()?;
+
+        let inverse = shifted_ranges(&edits)
+            .into_iter()
+            .zip(old_texts)
+            .map(|((start, end), old_text)| {
+                TextEdit::new(start, end, old_text)
+            })
+            .collect::<Vec<_>>();
+
+        // Apply the edits starting from the last one, so that the ranges of
+        // the other ones are still valid.
+        for (applied, edit) in edits.iter().rev().enumerate() {
+            let res = if applied == 0 {
+                self.replace_range(edit)
+            } else {
+                self.undojoin().and_then(|()| self.replace_range(edit))
+            };
+
+            if let Err(err) = res {
+                self.revert(&edits[edits.len() - applied..], &inverse);
+                return Err(err);
+            }
+        }
+
+        Ok(inverse)
+    }
+
+    /// Replaces the content of the buffer with the given text, only changing
+    /// the regions that differ from the current content.
+    ///
+    /// Unlike replacing all the lines with
+    /// [`set_lines`](Buffer::set_lines), this keeps the marks, extmarks and
+    /// folds outside of the changed regions, and the change is a single undo
+    /// step. A trailing newline in `new_text` doesn't add an empty line.
+    ///
+    /// If more than about a thousand lines differ, the region between the
+    /// first and last changed lines is replaced as a whole.
+    pub fn replace_contents_minimal(&mut self, new_text: &str) -> Result<()> {
+        let old = self
+            .get_lines(.., true)?
+            .map(|line| line.as_bytes().to_owned())
+            .collect::<Vec<_>>();
+
+        let old = old.iter().map(Vec::as_slice).collect::<Vec<_>>();
+
+        let new_text = new_text.strip_suffix('\n').unwrap_or(new_text);
+        let new = new_text.split('\n').collect::<Vec<_>>();
+
+        let new_bytes =
+            new.iter().map(|line| line.as_bytes()).collect::<Vec<_>>();
+
+        let edits = line_diff::diff(&old, &new_bytes)
+            .into_iter()
+            .map(|hunk| hunk_edit(&old, &new, hunk))
+            .collect::<Vec<_>>();
+
+        if !edits.is_empty() {
+            self.apply_edits(edits)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reverts the given applied edits, which are the last ones of the batch
+    /// whose inverse is `inverse`.
+    fn revert(&mut self, applied: &[TextEdit], inverse: &[TextEdit]) {
+        let old_texts = inverse[inverse.len() - applied.len()..]
+            .iter()
+            .map(|edit| &edit.new_text);
+
+        // Only the given edits have been applied, so their ranges have to be
+        // shifted independently of the other ones.
+        let undos = shifted_ranges(applied)
+            .into_iter()
+            .zip(old_texts)
+            .map(|((start, end), old_text)| {
+                TextEdit::new(start, end, old_text)
+            })
+            .collect::<Vec<_>>();
+
+        for undo in undos.iter().rev() {
+            let _ = self.undojoin();
+            let _ = self.replace_range(undo);
+        }
+    }
+
+    /// Returns the text between the given positions, failing with
+    /// [`Error::LineFromUtf8`] if a line isn't valid UTF-8.
+    pub(crate) fn get_range_text(
+        &self,
+        (start_row, start_col): (Row0, ByteCol),
+        (end_row, end_col): (Row0, ByteCol),
+    ) -> Result<String> {
+        let lines = self
+            .get_text(
+                start_row..end_row,
+                start_col,
+                end_col,
+                &GetTextOpts::default(),
+            )?
+            .zip(start_row.0..)
+            .map(|(line, row)| {
+                String::from_utf8(line.as_bytes().to_owned()).map_err(
+                    |source| Error::LineFromUtf8 { line: row, source },
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(lines.join("\n"))
+    }
+
+    fn replace_range(&mut self, edit: &TextEdit) -> Result<()> {
+        let (start_row, start_col) = edit.start;
+        let (end_row, end_col) = edit.end;
+        self.set_text(
+            start_row..end_row,
+            start_col,
+            end_col,
+            edit.new_text.split('\n'),
+        )
+    }
+
+    /// Joins the next change with the previous undo block.
+    fn undojoin(&self) -> Result<()> {
+        // `:undojoin` fails right after an undo, in which case there's
+        // nothing to join with anyway.
+        self.call(|()| crate::command("silent! undojoin"))
+    }
+}
+
+/// Checks that the sorted edits don't overlap and have valid ranges.
+fn validate(edits: &[TextEdit]) -> Result<()> {
+    if let Some(edit) = edits.iter().find(|edit| edit.start > edit.end) {
+        return Err(Error::custom(format!(
+            "edit starts at {:?} but ends at {:?}",
+            edit.start, edit.end
+        )));
+    }
+
+    if let Some(pair) =
+        edits.windows(2).find(|pair| pair[0].end > pair[1].start)
+    {
+        return Err(Error::custom(format!(
+            "edits at {:?}..{:?} and {:?}..{:?} overlap",
+            pair[0].start, pair[0].end, pair[1].start, pair[1].end
+        )));
+    }
+
+    Ok(())
+}
+
+/// Converts a hunk of a line diff to the edit that applies it.
+fn hunk_edit(old: &[&[u8]], new: &[&str], hunk: Hunk) -> TextEdit {
+    let new_text = new[hunk.new.clone()].join("\n");
+    let end_of = |row: usize| (Row0(row), ByteCol(old[row].len()));
+    let start_of = |row: usize| (Row0(row), ByteCol(0));
+
+    let Hunk { old: rows, new: new_rows } = hunk;
+
+    if rows.is_empty() {
+        // Inserting lines before an existing line, or after the last one.
+        return if rows.start < old.len() {
+            TextEdit::insert(start_of(rows.start), new_text + "\n")
+        } else {
+            TextEdit::insert(end_of(old.len() - 1), format!("\n{new_text}"))
+        };
+    }
+
+    if new_rows.is_empty() {
+        // Deleting lines including their newline, which is the previous one
+        // if the last line is deleted.
+        if rows.end < old.len() {
+            return TextEdit::delete(start_of(rows.start), start_of(rows.end));
+        } else if rows.start > 0 {
+            return TextEdit::delete(
+                end_of(rows.start - 1),
+                end_of(rows.end - 1),
+            );
+        }
+    }
+
+    trimmed_replacement(old, rows, &new_text)
+}
+
+/// Returns the edit replacing the given lines with the new text, without the
+/// prefix and suffix they have in common.
+fn trimmed_replacement(
+    old: &[&[u8]],
+    rows: Range<usize>,
+    new_text: &str,
+) -> TextEdit {
+    let old_text = old[rows.clone()].join(&b'\n');
+    let new_bytes = new_text.as_bytes();
+
+    let mut prefix =
+        old_text.iter().zip(new_bytes).take_while(|(a, b)| a == b).count();
+
+    while !new_text.is_char_boundary(prefix) {
+        prefix -= 1;
+    }
+
+    let mut suffix = old_text[prefix..]
+        .iter()
+        .rev()
+        .zip(new_bytes[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    while !new_text.is_char_boundary(new_text.len() - suffix) {
+        suffix -= 1;
+    }
+
+    TextEdit::new(
+        offset_to_pos(old, rows.start, prefix),
+        offset_to_pos(old, rows.start, old_text.len() - suffix),
+        &new_text[prefix..new_text.len() - suffix],
+    )
+}
+
+/// Converts a byte offset from the start of the given row to a position.
+fn offset_to_pos(
+    lines: &[&[u8]],
+    mut row: usize,
+    mut offset: usize,
+) -> (Row0, ByteCol) {
+    while offset > lines[row].len() {
+        offset -= lines[row].len() + 1;
+        row += 1;
+    }
+    (Row0(row), ByteCol(offset))
+}
+
+/// Returns the ranges spanned by the inserted text of the sorted edits once
+/// all of them have been applied.
+fn shifted_ranges(
+    edits: &[TextEdit],
+) -> Vec<((Row0, ByteCol), (Row0, ByteCol))> {
+    let mut ranges = Vec::with_capacity(edits.len());
+
+    // The end of the last edit before and after applying it.
+    let mut old_end = (Row0(0), ByteCol(0));
+    let mut new_end = (Row0(0), ByteCol(0));
+
+    for edit in edits {
+        let (row, col) = edit.start;
+
+        let start_row = Row0(row.0 + new_end.0.0 - old_end.0.0);
+
+        let start_col = if row == old_end.0 {
+            ByteCol(col.0 - old_end.1.0 + new_end.1.0)
+        } else {
+            col
+        };
+
+        let shifted =
+            TextEdit { start: (start_row, start_col), ..edit.clone() };
+
+        old_end = edit.end;
+        new_end = shifted.new_end();
+
+        ranges.push((shifted.start, new_end));
+    }
+
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(
+        start: (usize, usize),
+        end: (usize, usize),
+        text: &str,
+    ) -> TextEdit {
+        TextEdit::new(
+            (Row0(start.0), ByteCol(start.1)),
+            (Row0(end.0), ByteCol(end.1)),
+            text,
+        )
+    }
+
+    fn range(
+        start: (usize, usize),
+        end: (usize, usize),
+    ) -> ((Row0, ByteCol), (Row0, ByteCol)) {
+        ((Row0(start.0), ByteCol(start.1)), (Row0(end.0), ByteCol(end.1)))
+    }
+
+    fn hunk_edits(old: &[&str], new: &[&str]) -> Vec<TextEdit> {
+        let old = old.iter().map(|line| line.as_bytes()).collect::<Vec<_>>();
+        let new_bytes =
+            new.iter().map(|line| line.as_bytes()).collect::<Vec<_>>();
+        line_diff::diff(&old, &new_bytes)
+            .into_iter()
+            .map(|hunk| hunk_edit(&old, new, hunk))
+            .collect()
+    }
+
+    #[test]
+    fn hunk_edit_trims_common_text() {
+        assert_eq!(
+            hunk_edits(
+                &["foo", "let x = 1;", "bar"],
+                &["foo", "let x = 2;", "bar"]
+            ),
+            [edit((1, 8), (1, 9), "2")]
+        );
+    }
+
+    #[test]
+    fn hunk_edit_inserts_and_deletes_lines() {
+        assert_eq!(
+            hunk_edits(&["a", "c"], &["a", "b", "c"]),
+            [edit((1, 0), (1, 0), "b\n")]
+        );
+        assert_eq!(
+            hunk_edits(&["a"], &["a", "b"]),
+            [edit((0, 1), (0, 1), "\nb")]
+        );
+        assert_eq!(
+            hunk_edits(&["a", "b", "c"], &["a", "c"]),
+            [edit((1, 0), (2, 0), "")]
+        );
+        assert_eq!(
+            hunk_edits(&["a", "b"], &["a"]),
+            [edit((0, 1), (1, 1), "")]
+        );
+    }
+
+    #[test]
+    fn validate_overlap() {
+        let edits = [edit((0, 0), (0, 3), "a"), edit((0, 2), (0, 4), "b")];
+        assert!(validate(&edits).is_err());
+
+        let edits = [edit((0, 0), (0, 2), "a"), edit((0, 2), (0, 4), "b")];
+        assert!(validate(&edits).is_ok());
+
+        let edits = [edit((1, 0), (0, 2), "a")];
+        assert!(validate(&edits).is_err());
+    }
+
+    #[test]
+    fn shifted_ranges_same_line() {
+        // "foo bar baz" -> "x bar yy"
+        let edits = [edit((0, 0), (0, 3), "x"), edit((0, 8), (0, 11), "yy")];
+        assert_eq!(
+            shifted_ranges(&edits),
+            [range((0, 0), (0, 1)), range((0, 6), (0, 8))]
+        );
+    }
+
+    #[test]
+    fn shifted_ranges_multiline() {
+        // "foo\nbar\nbaz" -> "f\n1\n2o\nbar\nZ"
+        let edits =
+            [edit((0, 1), (0, 2), "\n1\n2"), edit((1, 2), (2, 3), "r\nZ")];
+        assert_eq!(
+            shifted_ranges(&edits),
+            [range((0, 1), (2, 1)), range((3, 2), (4, 1))]
+        );
+    }
+
+    #[test]
+    fn shifted_ranges_after_joined_lines() {
+        // "foo\nbar" -> "fobaX"
+        let edits = [edit((0, 2), (1, 0), ""), edit((1, 2), (1, 3), "X")];
+        assert_eq!(
+            shifted_ranges(&edits),
+            [range((0, 2), (0, 2)), range((0, 4), (0, 5))]
+        );
+    }
+}
@@ -46,6 +46,16 @@ impl_into!(StringOrInt, i32);
 impl_into!(StringOrInt, u32);
 impl_into!(StringOrInt, i64);
 
+impl StringOrInt for crate::types::StringOrInt {
+    #[inline]
+    fn to_object(self) -> Object {
+        match self {
+            Self::String(string) => string.into(),
+            Self::Int(int) => int.into(),
+        }
+    }
+}
+
 /// A trait implemented by strings and list of strings.
 pub trait StringOrListOfStrings {
     fn to_object(self) -> Object;
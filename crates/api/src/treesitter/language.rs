@@ -0,0 +1,292 @@
+use core::ffi::c_void;
+use std::path::{Path, PathBuf};
+
+use crate::utils::{call_vim_fn, call_vim_fn_n};
+use crate::{Error, Result};
+
+/// The signature of the function exported by a tree-sitter grammar, e.g.
+/// `tree_sitter_rust()`, returning a pointer to its `TSLanguage`.
+pub type LanguageFn = unsafe extern "C" fn() -> *const c_void;
+
+/// A tree-sitter grammar to be registered with Neovim via
+/// [`vim.treesitter.language.add()`][1].
+///
+/// # Examples
+///
+/// A grammar compiled into the plugin's own cdylib can be registered by
+/// passing its language function to [`from_symbol`](Language::from_symbol).
+/// The function has to be exported by the library, e.g. by re-exporting it
+/// with `#[unsafe(no_mangle)]`:
+///
+/// ```ignore
+/// use core::ffi::c_void;
+/// use nvim_oxi::api::treesitter::Language;
+///
+/// unsafe extern "C" {
+///     fn tree_sitter_foo_inner() -> *const c_void;
+/// }
+///
+/// #[unsafe(no_mangle)]
+/// pub unsafe extern "C" fn tree_sitter_foo() -> *const c_void {
+///     unsafe { tree_sitter_foo_inner() }
+/// }
+///
+/// Language::from_symbol("foo", tree_sitter_foo)?
+///     .filetypes(["foo", "foobar"])
+///     .add()?;
+/// ```
+///
+/// [1]: https://neovim.io/doc/user/treesitter.html#vim.treesitter.language.add()
+#[derive(Clone, Debug)]
+pub struct Language {
+    name: String,
+    path: PathBuf,
+    symbol_name: Option<String>,
+    filetypes: Vec<String>,
+}
+
+impl Language {
+    /// Creates a new language loaded from the parser library at the given
+    /// path.
+    #[inline]
+    pub fn from_path(
+        name: impl Into<String>,
+        path: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            path: path.into(),
+            symbol_name: None,
+            filetypes: Vec::new(),
+        }
+    }
+
+    /// Creates a new language from its language function, which must be
+    /// exported by the library it's defined in (usually the plugin itself)
+    /// with a name starting with `tree_sitter_`.
+    ///
+    /// The name of the function is recovered from the library's symbol
+    /// table. If that's not possible (e.g. on Windows), it defaults to
+    /// `tree_sitter_<name>` unless set with
+    /// [`symbol_name`](Language::symbol_name).
+    pub fn from_symbol(
+        name: impl Into<String>,
+        fun: LanguageFn,
+    ) -> Result<Self> {
+        let (path, symbol) = sys::symbol_infos(fun as *const c_void)?;
+
+        let symbol_name = match symbol {
+            Some(symbol) => symbol
+                .strip_prefix("tree_sitter_")
+                .map(ToOwned::to_owned)
+                .map(Some)
+                .ok_or_else(|| {
+                    Error::custom(format!(
+                        "the name of the language function should start with \
+                         `tree_sitter_`, got `{symbol}`"
+                    ))
+                })?,
+            None => None,
+        };
+
+        Ok(Self { symbol_name, ..Self::from_path(name, path) })
+    }
+
+    /// The filetypes to associate with the language.
+    #[inline]
+    pub fn filetypes<I, F>(mut self, filetypes: I) -> Self
+    where
+        I: IntoIterator<Item = F>,
+        F: Into<String>,
+    {
+        self.filetypes.extend(filetypes.into_iter().map(Into::into));
+        self
+    }
+
+    /// The name of the language.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The path of the parser library.
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The name of the language function without the `tree_sitter_` prefix,
+    /// if it's different from the name of the language.
+    #[inline]
+    pub fn symbol_name(mut self, symbol_name: impl Into<String>) -> Self {
+        self.symbol_name = Some(symbol_name.into());
+        self
+    }
+
+    /// Loads the parser and associates it with the configured filetypes.
+    pub fn add(&self) -> Result<()> {
+        let mut opts = types::Dictionary::new();
+        opts.insert("path", types::String::from(self.path.as_path()));
+        opts.insert("symbol_name", self.symbol_name.as_deref());
+
+        // On failure the function returns `nil` and an error message.
+        let (_, err): (Option<bool>, Option<String>) = call_vim_fn_n(
+            &[c"treesitter", c"language", c"add"],
+            (self.name.clone(), opts),
+            2,
+        )?;
+
+        if let Some(err) = err {
+            return Err(Error::custom(err));
+        }
+
+        if !self.filetypes.is_empty() {
+            register(&self.name, self.filetypes.iter().map(String::as_str))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Binding to [`vim.treesitter.language.get_lang()`][1].
+///
+/// Returns the language associated with the given filetype, if any.
+///
+/// [1]: https://neovim.io/doc/user/treesitter.html#vim.treesitter.language.get_lang()
+pub fn get_lang(filetype: &str) -> Result<Option<String>> {
+    call_vim_fn(
+        &[c"treesitter", c"language", c"get_lang"],
+        filetype.to_owned(),
+    )
+}
+
+/// Binding to [`vim.treesitter.language.register()`][1].
+///
+/// Associates the given filetypes with a language.
+///
+/// [1]: https://neovim.io/doc/user/treesitter.html#vim.treesitter.language.register()
+pub fn register<'a, I>(lang: &str, filetypes: I) -> Result<()>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let filetypes =
+        filetypes.into_iter().map(ToOwned::to_owned).collect::<Vec<_>>();
+    call_vim_fn(
+        &[c"treesitter", c"language", c"register"],
+        (lang.to_owned(), filetypes),
+    )
+}
+
+#[cfg(unix)]
+mod sys {
+    use core::ffi::{CStr, c_char, c_int, c_void};
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::PathBuf;
+
+    use crate::{Error, Result};
+
+    #[repr(C)]
+    struct DlInfo {
+        dli_fname: *const c_char,
+        dli_fbase: *mut c_void,
+        dli_sname: *const c_char,
+        dli_saddr: *mut c_void,
+    }
+
+    unsafe extern "C" {
+        fn dladdr(addr: *const c_void, info: *mut DlInfo) -> c_int;
+    }
+
+    /// Returns the path of the library containing the given symbol, and the
+    /// symbol's name if it's exported.
+    pub(super) fn symbol_infos(
+        addr: *const c_void,
+    ) -> Result<(PathBuf, Option<String>)> {
+        let mut info = DlInfo {
+            dli_fname: core::ptr::null(),
+            dli_fbase: core::ptr::null_mut(),
+            dli_sname: core::ptr::null(),
+            dli_saddr: core::ptr::null_mut(),
+        };
+
+        if unsafe { dladdr(addr, &mut info) } == 0 || info.dli_fname.is_null()
+        {
+            return Err(Error::custom("couldn't find the symbol's library"));
+        }
+
+        let path = unsafe { CStr::from_ptr(info.dli_fname) };
+        let path = PathBuf::from(OsStr::from_bytes(path.to_bytes()));
+
+        // `dladdr` returns the nearest exported symbol, which is only the one
+        // we're looking for if it starts at the same address.
+        let symbol = (!info.dli_sname.is_null()
+            && core::ptr::eq(info.dli_saddr, addr))
+        .then(|| unsafe { CStr::from_ptr(info.dli_sname) })
+        .map(|name| name.to_string_lossy().into_owned());
+
+        Ok((path, symbol))
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use core::ffi::c_void;
+    use std::ffi::OsString;
+    use std::os::windows::ffi::OsStringExt;
+    use std::path::PathBuf;
+
+    use crate::{Error, Result};
+
+    const GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS: u32 = 0x4;
+    const GET_MODULE_HANDLE_EX_FLAG_UNCHANGED_REFCOUNT: u32 = 0x2;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn GetModuleHandleExW(
+            flags: u32,
+            module_name: *const u16,
+            module: *mut *mut c_void,
+        ) -> i32;
+
+        fn GetModuleFileNameW(
+            module: *mut c_void,
+            filename: *mut u16,
+            size: u32,
+        ) -> u32;
+    }
+
+    /// Returns the path of the library containing the given symbol. The
+    /// symbol's name can't be recovered on Windows, so it has to be set
+    /// explicitly.
+    pub(super) fn symbol_infos(
+        addr: *const c_void,
+    ) -> Result<(PathBuf, Option<String>)> {
+        let mut module = core::ptr::null_mut();
+
+        let found = unsafe {
+            GetModuleHandleExW(
+                GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS
+                    | GET_MODULE_HANDLE_EX_FLAG_UNCHANGED_REFCOUNT,
+                addr as *const u16,
+                &mut module,
+            )
+        };
+
+        if found == 0 {
+            return Err(Error::custom("couldn't find the symbol's library"));
+        }
+
+        let mut buf = vec![0u16; 1024];
+        let len = unsafe {
+            GetModuleFileNameW(module, buf.as_mut_ptr(), buf.len() as u32)
+        };
+
+        if len == 0 {
+            return Err(Error::custom("couldn't get the library's path"));
+        }
+
+        buf.truncate(len as usize);
+        Ok((OsString::from_wide(&buf).into(), None))
+    }
+}
@@ -0,0 +1,75 @@
+use super::{Node, Range};
+use crate::utils::{LuaValue, call_vim_fn};
+use crate::{Buffer, Error, Result};
+
+/// A handle to the parser of a buffer, i.e. a Lua [`LanguageTree`][1].
+///
+/// [1]: https://neovim.io/doc/user/treesitter.html#LanguageTree
+#[derive(Clone)]
+pub struct LanguageTree(LuaValue);
+
+/// A handle to a syntax tree, i.e. a Lua [`TSTree`][1].
+///
+/// [1]: https://neovim.io/doc/user/treesitter.html#TSTree
+#[derive(Clone)]
+pub struct Tree(LuaValue);
+
+impl LanguageTree {
+    /// Binding to [`vim.treesitter.get_parser()`][1].
+    ///
+    /// Returns the parser of the given buffer, for the given language or the
+    /// one of the buffer's filetype if `None`.
+    ///
+    /// [1]: https://neovim.io/doc/user/treesitter.html#vim.treesitter.get_parser()
+    pub fn get(buffer: &Buffer, lang: Option<&str>) -> Result<Self> {
+        let opts = types::Dictionary::from_iter([("error", false)]);
+        let parser: Option<LuaValue> = call_vim_fn(
+            &[c"treesitter", c"get_parser"],
+            (buffer.clone(), lang.map(ToOwned::to_owned), opts),
+        )?;
+        parser
+            .map(Self)
+            .ok_or_else(|| Error::custom("no parser found for the buffer"))
+    }
+
+    /// Returns the language of the tree.
+    pub fn lang(&self) -> Result<String> {
+        self.0.call_method(c"lang", (), 1)
+    }
+
+    /// Returns the smallest named node spanning the given range.
+    pub fn named_node_for_range(&self, range: Range) -> Result<Option<Node>> {
+        let range = vec![
+            range.start_row.0,
+            range.start_col.0,
+            range.end_row.0,
+            range.end_col.0,
+        ];
+        let node: Option<LuaValue> =
+            self.0.call_method(c"named_node_for_range", range, 1)?;
+        Ok(node.map(Node))
+    }
+
+    /// Parses the buffer if it changed since the last parse, returning the
+    /// resulting trees.
+    pub fn parse(&self) -> Result<Vec<Tree>> {
+        let trees: Vec<LuaValue> = self.0.call_method(c"parse", (), 1)?;
+        Ok(trees.into_iter().map(Tree).collect())
+    }
+
+    /// Parses the buffer and returns the root node of its main tree.
+    pub fn root(&self) -> Result<Node> {
+        self.parse()?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::custom("the parser returned no tree"))?
+            .root()
+    }
+}
+
+impl Tree {
+    /// Returns the root node of the tree.
+    pub fn root(&self) -> Result<Node> {
+        self.0.call_method(c"root", (), 1).map(Node)
+    }
+}
@@ -0,0 +1,20 @@
+//! Bindings to the [`vim.treesitter`][1] Lua module.
+//!
+//! The values returned by tree-sitter (like nodes and trees) live in Lua, and
+//! the types in this module are handles to them. They don't borrow the
+//! buffer they come from, so a node can outlive the tree it belongs to if the
+//! buffer is reparsed, in which case its methods may return outdated results.
+//!
+//! [1]: https://neovim.io/doc/user/treesitter.html
+
+mod language;
+mod language_tree;
+mod node;
+mod query;
+mod range;
+
+pub use language::*;
+pub use language_tree::*;
+pub use node::*;
+pub use query::*;
+pub use range::*;
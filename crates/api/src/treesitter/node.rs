@@ -0,0 +1,139 @@
+use core::fmt;
+
+use super::Range;
+use crate::utils::LuaValue;
+use crate::{Buffer, Result};
+
+/// A handle to a tree-sitter node, i.e. a Lua [`TSNode`][1].
+///
+/// [1]: https://neovim.io/doc/user/treesitter.html#TSNode
+#[derive(Clone)]
+pub struct Node(pub(super) LuaValue);
+
+impl fmt::Debug for Node {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.kind(), self.range()) {
+            (Ok(kind), Ok(range)) => f
+                .debug_struct("Node")
+                .field("kind", &kind)
+                .field("range", &range)
+                .finish(),
+            (Err(err), _) | (_, Err(err)) => {
+                f.debug_tuple("Node").field(&err).finish()
+            },
+        }
+    }
+}
+
+impl PartialEq for Node {
+    /// Same as [`Node::equal`], except that nodes that couldn't be compared
+    /// are considered different.
+    fn eq(&self, other: &Self) -> bool {
+        self.equal(other).unwrap_or(false)
+    }
+}
+
+impl Node {
+    /// Returns the child at the given index, counting both named and
+    /// anonymous children.
+    pub fn child(&self, index: usize) -> Result<Option<Self>> {
+        self.0.call_method(c"child", index, 1).map(wrap)
+    }
+
+    /// Returns the number of children of the node, counting both named and
+    /// anonymous children.
+    pub fn child_count(&self) -> Result<usize> {
+        self.0.call_method(c"child_count", (), 1)
+    }
+
+    /// Returns all the children of the node, both named and anonymous.
+    pub fn children(&self) -> Result<Vec<Self>> {
+        (0..self.child_count()?)
+            .filter_map(|idx| self.child(idx).transpose())
+            .collect()
+    }
+
+    /// Whether the two nodes are the same node of the same tree.
+    pub fn equal(&self, other: &Self) -> Result<bool> {
+        self.0.call_method(c"equal", other.0.clone(), 1)
+    }
+
+    /// Returns the children of the node for the given field name, as
+    /// defined by the grammar.
+    pub fn field(&self, name: &str) -> Result<Vec<Self>> {
+        let nodes: Vec<LuaValue> =
+            self.0.call_method(c"field", name.to_owned(), 1)?;
+        Ok(nodes.into_iter().map(Self).collect())
+    }
+
+    /// Whether the node contains a syntax error.
+    pub fn has_error(&self) -> Result<bool> {
+        self.0.call_method(c"has_error", (), 1)
+    }
+
+    /// Returns a unique identifier for the node within its tree.
+    pub fn id(&self) -> Result<String> {
+        self.0.call_method(c"id", (), 1)
+    }
+
+    /// Whether the node was inserted by the parser to recover from an
+    /// error.
+    pub fn is_missing(&self) -> Result<bool> {
+        self.0.call_method(c"missing", (), 1)
+    }
+
+    /// Whether the node is named.
+    pub fn is_named(&self) -> Result<bool> {
+        self.0.call_method(c"named", (), 1)
+    }
+
+    /// Returns the type of the node, e.g. `"function_declaration"`.
+    pub fn kind(&self) -> Result<String> {
+        self.0.call_method(c"type", (), 1)
+    }
+
+    /// Returns the named child at the given index.
+    pub fn named_child(&self, index: usize) -> Result<Option<Self>> {
+        self.0.call_method(c"named_child", index, 1).map(wrap)
+    }
+
+    /// Returns the number of named children of the node.
+    pub fn named_child_count(&self) -> Result<usize> {
+        self.0.call_method(c"named_child_count", (), 1)
+    }
+
+    /// Returns the named children of the node.
+    pub fn named_children(&self) -> Result<Vec<Self>> {
+        (0..self.named_child_count()?)
+            .filter_map(|idx| self.named_child(idx).transpose())
+            .collect()
+    }
+
+    /// Returns the parent of the node, or `None` for the root node.
+    pub fn parent(&self) -> Result<Option<Self>> {
+        self.0.call_method(c"parent", (), 1).map(wrap)
+    }
+
+    /// Returns the range spanned by the node.
+    pub fn range(&self) -> Result<Range> {
+        self.0
+            .call_method::<_, (usize, usize, usize, usize)>(c"range", (), 4)
+            .map(Into::into)
+    }
+
+    /// Returns an S-expression representing the node.
+    pub fn sexpr(&self) -> Result<String> {
+        self.0.call_method(c"sexpr", (), 1)
+    }
+
+    /// Returns the text of the buffer spanned by the node, like
+    /// [`Range::text`].
+    pub fn text(&self, buffer: &Buffer) -> Result<String> {
+        self.range()?.text(buffer)
+    }
+}
+
+#[inline]
+fn wrap(value: Option<LuaValue>) -> Option<Node> {
+    value.map(Node)
+}
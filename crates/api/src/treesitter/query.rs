@@ -0,0 +1,108 @@
+use core::ops::{Bound, RangeBounds};
+
+use super::Node;
+use crate::types::Row0;
+use crate::utils::{LuaValue, call_vim_fn};
+use crate::{Buffer, Error, Result};
+
+/// A parsed tree-sitter query, i.e. a Lua [`Query`][1].
+///
+/// [1]: https://neovim.io/doc/user/treesitter.html#Query
+#[derive(Clone)]
+pub struct Query(LuaValue);
+
+/// A node captured by a [`Query`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryCapture {
+    /// The name of the capture, without the leading `@`.
+    pub name: String,
+
+    /// The captured node.
+    pub node: Node,
+}
+
+impl Query {
+    /// Binding to [`vim.treesitter.query.get()`][1].
+    ///
+    /// Returns the runtime query with the given name (e.g. `"highlights"`)
+    /// for the given language, if any.
+    ///
+    /// [1]: https://neovim.io/doc/user/treesitter.html#vim.treesitter.query.get()
+    pub fn get(lang: &str, name: &str) -> Result<Option<Self>> {
+        call_vim_fn::<_, Option<LuaValue>>(
+            &[c"treesitter", c"query", c"get"],
+            (lang.to_owned(), name.to_owned()),
+        )
+        .map(|query| query.map(Self))
+    }
+
+    /// Binding to [`vim.treesitter.query.parse()`][1].
+    ///
+    /// Parses a query written in the tree-sitter query language.
+    ///
+    /// [1]: https://neovim.io/doc/user/treesitter.html#vim.treesitter.query.parse()
+    pub fn parse(lang: &str, source: &str) -> Result<Self> {
+        call_vim_fn(
+            &[c"treesitter", c"query", c"parse"],
+            (lang.to_owned(), source.to_owned()),
+        )
+        .map(Self)
+    }
+
+    /// Returns the names of the captures defined in the query, indexed by
+    /// capture ID minus one.
+    pub fn capture_names(&self) -> Result<Vec<String>> {
+        self.0.get_field(c"captures")
+    }
+
+    /// Returns the nodes captured by the query within the given node, in the
+    /// order they appear in the buffer. The row range can be used to only
+    /// look at a part of the node, e.g. the visible lines.
+    pub fn captures<R>(
+        &self,
+        node: &Node,
+        buffer: &Buffer,
+        rows: R,
+    ) -> Result<Vec<QueryCapture>>
+    where
+        R: RangeBounds<Row0>,
+    {
+        let start = match rows.start_bound() {
+            Bound::Unbounded => None,
+            Bound::Included(&Row0(n)) => Some(n),
+            Bound::Excluded(&Row0(n)) => Some(n + 1),
+        };
+
+        let stop = match rows.end_bound() {
+            Bound::Unbounded => None,
+            Bound::Included(&Row0(n)) => Some(n + 1),
+            Bound::Excluded(&Row0(n)) => Some(n),
+        };
+
+        let names = self.capture_names()?;
+
+        let iter: LuaValue = self.0.call_method(
+            c"iter_captures",
+            (node.0.clone(), buffer.clone(), start, stop),
+            1,
+        )?;
+
+        let mut captures = Vec::new();
+
+        while let (Some(id), Some(node)) =
+            iter.call::<_, (Option<usize>, Option<LuaValue>)>((), 2)?
+        {
+            // Capture IDs are 1-based indices into the capture names.
+            let name = id
+                .checked_sub(1)
+                .and_then(|idx| names.get(idx))
+                .cloned()
+                .ok_or_else(|| {
+                    Error::custom(format!("unknown capture ID {id}"))
+                })?;
+            captures.push(QueryCapture { name, node: Node(node) });
+        }
+
+        Ok(captures)
+    }
+}
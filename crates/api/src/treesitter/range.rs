@@ -0,0 +1,91 @@
+use crate::types::{ByteCol, Row0};
+use crate::{Buffer, Result};
+
+/// The range spanned by a tree-sitter [`Node`](super::Node).
+///
+/// Rows and columns are 0-indexed byte positions, like the ones used by
+/// [`Buffer::get_text`] and [`Buffer::set_extmark`]. The end position is
+/// exclusive.
+#[derive(
+    Copy, Clone, Debug, Default, Eq, PartialEq, Hash, PartialOrd, Ord,
+)]
+pub struct Range {
+    pub start_row: Row0,
+    pub start_col: ByteCol,
+    pub end_row: Row0,
+    pub end_col: ByteCol,
+}
+
+impl Range {
+    /// Creates a new range.
+    #[inline]
+    pub fn new(
+        start_row: Row0,
+        start_col: ByteCol,
+        end_row: Row0,
+        end_col: ByteCol,
+    ) -> Self {
+        Self { start_row, start_col, end_row, end_col }
+    }
+
+    /// Whether the range contains the given position.
+    #[inline]
+    pub fn contains(&self, row: Row0, col: ByteCol) -> bool {
+        self.start() <= (row, col) && (row, col) < self.end()
+    }
+
+    /// Returns the `(row, col)` end position of the range.
+    #[inline]
+    pub fn end(&self) -> (Row0, ByteCol) {
+        (self.end_row, self.end_col)
+    }
+
+    /// Whether the range is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.start() >= self.end()
+    }
+
+    /// Returns the `(row, col)` start position of the range.
+    #[inline]
+    pub fn start(&self) -> (Row0, ByteCol) {
+        (self.start_row, self.start_col)
+    }
+
+    /// Returns the text of the buffer spanned by the range, with lines
+    /// joined by `\n`.
+    ///
+    /// Like in `vim.treesitter.get_node_text()`, a range ending after the
+    /// last line (e.g. the one of a root node) stops at the end of the last
+    /// line. Fails with [`Error::LineFromUtf8`](crate::Error::LineFromUtf8)
+    /// if a line isn't valid UTF-8.
+    pub fn text(&self, buffer: &Buffer) -> Result<String> {
+        let line_count = buffer.line_count()?;
+        let mut end = self.end();
+
+        if end.0.0 >= line_count {
+            let last_row = Row0(line_count.saturating_sub(1));
+            let len = buffer
+                .get_lines(last_row..Row0(last_row.0 + 1), true)?
+                .next()
+                .map_or(0, |line| line.as_bytes().len());
+            end = (last_row, ByteCol(len));
+        }
+
+        buffer.get_range_text(self.start().min(end), end)
+    }
+}
+
+impl From<(usize, usize, usize, usize)> for Range {
+    #[inline]
+    fn from(
+        (start_row, start_col, end_row, end_col): (usize, usize, usize, usize),
+    ) -> Self {
+        Self {
+            start_row: Row0(start_row),
+            start_col: ByteCol(start_col),
+            end_row: Row0(end_row),
+            end_col: ByteCol(end_col),
+        }
+    }
+}
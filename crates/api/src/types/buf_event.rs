@@ -0,0 +1,228 @@
+use super::{ByteCol, PositionEncoding, Row0};
+use crate::Buffer;
+use crate::opts::{
+    OnBytesArgs,
+    OnChangedtickArgs,
+    OnDetachArgs,
+    OnLinesArgs,
+    OnReloadArgs,
+};
+
+/// An event received by the callback registered with
+/// [`BufAttachOptsBuilder::on_event`](crate::opts::BufAttachOptsBuilder::on_event).
+///
+/// Every change to the buffer's text triggers both a [`Lines`](Self::Lines)
+/// and a [`Bytes`](Self::Bytes) event.
+#[non_exhaustive]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BufEvent {
+    /// A range of lines was replaced.
+    Lines {
+        /// The buffer that changed.
+        buffer: Buffer,
+
+        /// The value of the buffer's `b:changedtick` after the change.
+        changedtick: u32,
+
+        /// The first line that changed.
+        first: Row0,
+
+        /// The line after the last one that changed, before the change.
+        last_old: Row0,
+
+        /// The line after the last one that changed, after the change.
+        last_new: Row0,
+
+        /// The size in bytes of the replaced lines.
+        old_byte_size: usize,
+
+        /// The number of UTF-32 codepoints of the replaced lines, if
+        /// [`utf_sizes`](crate::opts::BufAttachOptsBuilder::utf_sizes) is
+        /// set.
+        old_utf32_size: Option<usize>,
+
+        /// The number of UTF-16 codeunits of the replaced lines, if
+        /// [`utf_sizes`](crate::opts::BufAttachOptsBuilder::utf_sizes) is
+        /// set.
+        old_utf16_size: Option<usize>,
+    },
+
+    /// A range of text was replaced.
+    ///
+    /// The end positions are `(rows, cols)` extents relative to `start`, so
+    /// they're left as bare `usize`s: their column is only an offset from
+    /// `start`'s column if their row is `0`.
+    Bytes {
+        /// The buffer that changed.
+        buffer: Buffer,
+
+        /// The value of the buffer's `b:changedtick` after the change.
+        changedtick: u32,
+
+        /// The start position of the replaced text.
+        start: (Row0, ByteCol),
+
+        /// The byte offset of `start` from the beginning of the buffer.
+        start_byte: usize,
+
+        /// The extent of the replaced text.
+        old_end: (usize, usize),
+
+        /// The length in bytes of the replaced text.
+        old_end_byte: usize,
+
+        /// The extent of the new text.
+        new_end: (usize, usize),
+
+        /// The length in bytes of the new text.
+        new_end_byte: usize,
+    },
+
+    /// The buffer's `b:changedtick` was incremented without the text
+    /// changing.
+    ChangedTick {
+        /// The buffer whose changedtick was incremented.
+        buffer: Buffer,
+
+        /// The new value of `b:changedtick`.
+        changedtick: u32,
+    },
+
+    /// The buffer was reloaded, and its entire content should be considered
+    /// changed.
+    Reload {
+        /// The buffer that was reloaded.
+        buffer: Buffer,
+    },
+
+    /// The callbacks were detached from the buffer.
+    Detach {
+        /// The buffer the callbacks were detached from.
+        buffer: Buffer,
+    },
+}
+
+impl BufEvent {
+    #[inline]
+    pub(crate) fn reload((_, buffer): OnReloadArgs) -> Self {
+        Self::Reload { buffer }
+    }
+
+    #[inline]
+    pub(crate) fn detach((_, buffer): OnDetachArgs) -> Self {
+        Self::Detach { buffer }
+    }
+
+    /// Returns the buffer that triggered the event.
+    #[inline]
+    pub fn buffer(&self) -> &Buffer {
+        match self {
+            Self::Lines { buffer, .. }
+            | Self::Bytes { buffer, .. }
+            | Self::ChangedTick { buffer, .. }
+            | Self::Reload { buffer }
+            | Self::Detach { buffer } => buffer,
+        }
+    }
+
+    /// Returns the buffer's `b:changedtick` after the event, if it carries
+    /// one.
+    #[inline]
+    pub fn changedtick(&self) -> Option<u32> {
+        match self {
+            Self::Lines { changedtick, .. }
+            | Self::Bytes { changedtick, .. }
+            | Self::ChangedTick { changedtick, .. } => Some(*changedtick),
+            Self::Reload { .. } | Self::Detach { .. } => None,
+        }
+    }
+
+    /// Returns the size of the text replaced by a [`Lines`](Self::Lines)
+    /// event in the given encoding. The UTF-16 and UTF-32 sizes are only
+    /// available if
+    /// [`utf_sizes`](crate::opts::BufAttachOptsBuilder::utf_sizes) is set.
+    ///
+    /// This can be used to keep offsets measured in that encoding up to date
+    /// without reading the old text.
+    #[inline]
+    pub fn old_size(&self, encoding: PositionEncoding) -> Option<usize> {
+        match (self, encoding) {
+            (Self::Lines { old_byte_size, .. }, PositionEncoding::Utf8) => {
+                Some(*old_byte_size)
+            },
+            (Self::Lines { old_utf16_size, .. }, PositionEncoding::Utf16) => {
+                *old_utf16_size
+            },
+            (Self::Lines { old_utf32_size, .. }, PositionEncoding::Utf32) => {
+                *old_utf32_size
+            },
+            _ => None,
+        }
+    }
+}
+
+impl From<OnLinesArgs> for BufEvent {
+    #[inline]
+    fn from(
+        (
+            _,
+            buffer,
+            changedtick,
+            first,
+            last_old,
+            last_new,
+            old_byte_size,
+            old_utf32_size,
+            old_utf16_size,
+        ): OnLinesArgs,
+    ) -> Self {
+        Self::Lines {
+            buffer,
+            changedtick,
+            first: Row0(first),
+            last_old: Row0(last_old),
+            last_new: Row0(last_new),
+            old_byte_size,
+            old_utf32_size,
+            old_utf16_size,
+        }
+    }
+}
+
+impl From<OnBytesArgs> for BufEvent {
+    #[inline]
+    fn from(
+        (
+            _,
+            buffer,
+            changedtick,
+            start_row,
+            start_col,
+            start_byte,
+            old_end_row,
+            old_end_col,
+            old_end_byte,
+            new_end_row,
+            new_end_col,
+            new_end_byte,
+        ): OnBytesArgs,
+    ) -> Self {
+        Self::Bytes {
+            buffer,
+            changedtick,
+            start: (Row0(start_row), ByteCol(start_col)),
+            start_byte,
+            old_end: (old_end_row, old_end_col),
+            old_end_byte,
+            new_end: (new_end_row, new_end_col),
+            new_end_byte,
+        }
+    }
+}
+
+impl From<OnChangedtickArgs> for BufEvent {
+    #[inline]
+    fn from((_, buffer, changedtick): OnChangedtickArgs) -> Self {
+        Self::ChangedTick { buffer, changedtick }
+    }
+}
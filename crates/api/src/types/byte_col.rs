@@ -0,0 +1,47 @@
+use serde::Deserialize;
+use types::{
+    Integer,
+    Object,
+    conversion::{self, FromObject},
+};
+
+/// A 0-indexed column measured in bytes, which is how every Neovim API
+/// function indexes into a line.
+///
+/// See [`Position`](super::Position) to convert it to and from UTF-16 or
+/// UTF-32 columns.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Deserialize,
+)]
+#[serde(transparent)]
+pub struct ByteCol(pub usize);
+
+impl From<ByteCol> for usize {
+    #[inline]
+    fn from(col: ByteCol) -> Self {
+        col.0
+    }
+}
+
+impl From<ByteCol> for Object {
+    #[inline]
+    fn from(col: ByteCol) -> Self {
+        (col.0 as Integer).into()
+    }
+}
+
+impl FromObject for ByteCol {
+    #[inline]
+    fn from_object(obj: Object) -> Result<Self, conversion::Error> {
+        usize::from_object(obj).map(Self)
+    }
+}
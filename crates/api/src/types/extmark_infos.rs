@@ -1,24 +1,49 @@
 use serde::Deserialize;
+use serde::de::IntoDeserializer;
 use types::{
     Object,
     conversion::{self, FromObject},
     serde::Deserializer,
 };
 
-use super::{ExtmarkHlMode, ExtmarkVirtTextChunk, ExtmarkVirtTextPosition};
+use super::{
+    ByteCol,
+    ExtmarkHlMode,
+    ExtmarkVirtTextChunk,
+    ExtmarkVirtTextPosition,
+    Row0,
+    StringOrInt,
+    VirtLinesOverflow,
+};
 
-/// Extmark infos returned by `Buffer::get_extmark_by_id`.
+/// Extmark infos returned by `Buffer::get_extmark_by_id` and
+/// `Buffer::get_extmarks` when the `details` option is set.
+///
+/// Highlight groups are returned as names, unless the `hl_name` option is
+/// set to `false`. The infos can be turned back into the options that
+/// recreate the extmark with
+/// [`SetExtmarkOpts::from`](crate::opts::SetExtmarkOpts).
 #[non_exhaustive]
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
 pub struct ExtmarkInfos {
     #[serde(default)]
-    pub end_col: Option<usize>,
+    pub conceal: Option<String>,
+
+    /// Whether the lines spanned by the extmark are concealed.
+    #[serde(default, deserialize_with = "is_present")]
+    pub conceal_lines: bool,
+
+    #[serde(default)]
+    pub cursorline_hl_group: Option<StringOrInt>,
+
+    #[serde(default)]
+    pub end_col: Option<ByteCol>,
 
     #[serde(default)]
     pub end_right_gravity: Option<bool>,
 
     #[serde(default)]
-    pub end_row: Option<usize>,
+    pub end_row: Option<Row0>,
 
     #[serde(default)]
     pub hl_eol: Option<bool>,
@@ -29,16 +54,52 @@ pub struct ExtmarkInfos {
     #[serde(default)]
     pub hl_mode: Option<ExtmarkHlMode>,
 
+    /// Whether the extmark was invalidated because the text it spanned was
+    /// deleted. Only returned for extmarks created with `invalidate` set.
+    #[serde(default)]
+    pub invalid: Option<bool>,
+
+    #[serde(default)]
+    pub invalidate: Option<bool>,
+
+    #[serde(default)]
+    pub line_hl_group: Option<StringOrInt>,
+
+    #[serde(default)]
+    pub ns_id: Option<u32>,
+
+    #[serde(default)]
+    pub number_hl_group: Option<StringOrInt>,
+
     #[serde(default)]
     pub priority: Option<u32>,
 
     pub right_gravity: bool,
 
+    #[serde(default)]
+    pub sign_hl_group: Option<StringOrInt>,
+
+    /// The name of the legacy sign the extmark was created from, if any.
+    #[serde(default)]
+    pub sign_name: Option<String>,
+
+    #[serde(default)]
+    pub sign_text: Option<String>,
+
+    #[serde(default)]
+    pub spell: Option<bool>,
+
     #[serde(default)]
     pub ui_watched: Option<bool>,
 
     #[serde(default)]
-    pub virt_lines: Option<Vec<Vec<(String, String)>>>,
+    pub undo_restore: Option<bool>,
+
+    #[serde(default)]
+    pub url: Option<String>,
+
+    #[serde(default)]
+    pub virt_lines: Option<Vec<Vec<ExtmarkVirtTextChunk>>>,
 
     #[serde(default)]
     pub virt_lines_above: Option<bool>,
@@ -46,19 +107,51 @@ pub struct ExtmarkInfos {
     #[serde(default)]
     pub virt_lines_leftcol: Option<bool>,
 
+    #[serde(default)]
+    pub virt_lines_overflow: Option<VirtLinesOverflow>,
+
     #[serde(default)]
     pub virt_text: Vec<ExtmarkVirtTextChunk>,
 
     #[serde(default)]
     pub virt_text_hide: Option<bool>,
 
-    #[serde(default)]
+    /// `None` for virtual text placed with `virt_text_win_col`.
+    #[serde(default, deserialize_with = "virt_text_pos")]
     pub virt_text_pos: Option<ExtmarkVirtTextPosition>,
 
+    #[serde(default)]
+    pub virt_text_repeat_linebreak: Option<bool>,
+
     #[serde(default)]
     pub virt_text_win_col: Option<i64>,
 }
 
+/// Neovim signals some flags by including a key with an empty string value.
+fn is_present<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    serde::de::IgnoredAny::deserialize(deserializer).map(|_| true)
+}
+
+/// Neovim reports virtual text placed at a fixed window column with a
+/// `"win_col"` position, which can't be passed back to it.
+fn virt_text_pos<'de, D>(
+    deserializer: D,
+) -> Result<Option<ExtmarkVirtTextPosition>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match Option::<String>::deserialize(deserializer)? {
+        Some(pos) if pos != "win_col" => {
+            ExtmarkVirtTextPosition::deserialize(pos.into_deserializer())
+                .map(Some)
+        },
+        _ => Ok(None),
+    }
+}
+
 impl FromObject for ExtmarkInfos {
     fn from_object(obj: Object) -> Result<Self, conversion::Error> {
         Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
@@ -1,12 +1,14 @@
 use serde::Deserialize;
 use types::{Array, Integer, Object};
 
+use super::{ByteCol, Row0};
+
 #[non_exhaustive]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
 pub enum ExtmarkPosition {
     /// Defines the extmark's position in the buffer by a 0-indexed `(row,
     /// col)` tuple.
-    ByTuple((usize, usize)),
+    ByTuple((Row0, ByteCol)),
 
     /// Defines the extmark's position in the buffer by its id.
     ById(u32),
@@ -17,7 +19,7 @@ impl From<ExtmarkPosition> for Object {
         use ExtmarkPosition::*;
 
         match pos {
-            ByTuple((row, col)) => {
+            ByTuple((Row0(row), ByteCol(col))) => {
                 Array::from_iter([row as Integer, col as Integer]).into()
             },
             ById(extmark_id) => extmark_id.into(),
@@ -0,0 +1,11 @@
+/// The direction an [`Anchor`](crate::Anchor) moves in when text is inserted
+/// at its position.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Gravity {
+    /// The anchor stays before the inserted text.
+    Left,
+
+    /// The anchor moves after the inserted text.
+    #[default]
+    Right,
+}
@@ -0,0 +1,216 @@
+use serde::{Deserialize, Serialize};
+
+use super::{HighlightInfos, Rgb};
+
+/// The definition of a highlight group, with typed colors.
+///
+/// It can be created from the [`HighlightInfos`] returned by
+/// [`get_hl`](crate::get_hl), and turned into the
+/// [`SetHighlightOpts`](crate::opts::SetHighlightOpts) passed to
+/// [`set_hl`](crate::set_hl). Only the attributes that are set are included
+/// when it's serialized.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nvim_oxi::api::{self, opts::SetHighlightOpts, types::*};
+///
+/// let bg = Rgb::new(0x1e, 0x1e, 0x2e);
+///
+/// let hl = Highlight {
+///     fg: bg.most_contrasting([Rgb::WHITE, Rgb::BLACK]),
+///     bg: Some(bg.lighten(0.1)),
+///     bold: true,
+///     ..Default::default()
+/// };
+///
+/// api::set_hl(0, "MyPluginTitle", &SetHighlightOpts::from(&hl))?;
+/// ```
+#[derive(
+    Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct Highlight {
+    /// The name of the group this one is linked to. Neovim ignores the other
+    /// attributes of a linked group.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fg: Option<Rgb>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bg: Option<Rgb>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sp: Option<Rgb>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ctermfg: Option<u8>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ctermbg: Option<u8>,
+
+    /// The transparency of the group's background, between 0 and 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blend: Option<u8>,
+
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
+    pub bold: bool,
+
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
+    pub standout: bool,
+
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
+    pub strikethrough: bool,
+
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
+    pub underline: bool,
+
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
+    pub undercurl: bool,
+
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
+    pub underdouble: bool,
+
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
+    pub underdotted: bool,
+
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
+    pub underdashed: bool,
+
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
+    pub italic: bool,
+
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
+    pub reverse: bool,
+
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
+    pub altfont: bool,
+
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
+    pub nocombine: bool,
+
+    /// Whether the definition only applies if the group isn't already
+    /// defined.
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
+    pub default: bool,
+}
+
+impl Highlight {
+    /// Creates a group linked to the given one.
+    #[inline]
+    pub fn linked_to(group: impl Into<String>) -> Self {
+        Self { link: Some(group.into()), ..Default::default() }
+    }
+}
+
+impl From<&HighlightInfos> for Highlight {
+    fn from(infos: &HighlightInfos) -> Self {
+        let is_set = |attr: Option<bool>| attr.unwrap_or(false);
+
+        Self {
+            link: infos.link.clone(),
+            fg: infos.foreground.map(Rgb::from),
+            bg: infos.background.map(Rgb::from),
+            sp: infos.special.map(Rgb::from),
+            ctermfg: infos.ctermfg,
+            ctermbg: infos.ctermbg,
+            blend: infos.blend.and_then(|blend| u8::try_from(blend).ok()),
+            bold: is_set(infos.bold),
+            standout: is_set(infos.standout),
+            strikethrough: is_set(infos.strikethrough),
+            underline: is_set(infos.underline),
+            undercurl: is_set(infos.undercurl),
+            underdouble: is_set(infos.underdouble.or(infos.underlineline)),
+            underdotted: is_set(infos.underdotted.or(infos.underdot)),
+            underdashed: is_set(infos.underdashed.or(infos.underdash)),
+            italic: is_set(infos.italic),
+            reverse: is_set(infos.reverse),
+            altfont: is_set(infos.altfont),
+            nocombine: is_set(infos.nocombine),
+            default: is_set(infos.default),
+        }
+    }
+}
+
+impl From<HighlightInfos> for Highlight {
+    #[inline]
+    fn from(infos: HighlightInfos) -> Self {
+        Self::from(&infos)
+    }
+}
+
+impl From<&Highlight> for HighlightInfos {
+    fn from(hl: &Highlight) -> Self {
+        // Like Neovim, only include the attributes that are set.
+        let attr = |is_set: bool| is_set.then_some(true);
+
+        Self {
+            link: hl.link.clone(),
+            foreground: hl.fg.map(u32::from),
+            background: hl.bg.map(u32::from),
+            special: hl.sp.map(u32::from),
+            ctermfg: hl.ctermfg,
+            ctermbg: hl.ctermbg,
+            blend: hl.blend.map(u32::from),
+            bold: attr(hl.bold),
+            standout: attr(hl.standout),
+            strikethrough: attr(hl.strikethrough),
+            underline: attr(hl.underline),
+            undercurl: attr(hl.undercurl),
+            underdouble: attr(hl.underdouble),
+            underdotted: attr(hl.underdotted),
+            underdashed: attr(hl.underdashed),
+            italic: attr(hl.italic),
+            reverse: attr(hl.reverse),
+            altfont: attr(hl.altfont),
+            nocombine: attr(hl.nocombine),
+            default: attr(hl.default),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infos_round_trip() {
+        let hl = Highlight {
+            fg: Some(Rgb::new(1, 2, 3)),
+            ctermbg: Some(208),
+            blend: Some(20),
+            bold: true,
+            underdotted: true,
+            ..Default::default()
+        };
+
+        let infos = HighlightInfos::from(&hl);
+        assert_eq!(infos.foreground, Some(0x010203));
+        assert_eq!(infos.italic, None);
+        assert_eq!(Highlight::from(&infos), hl);
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        use serde::{Deserialize, Serialize};
+        use types::serde::{Deserializer, Serializer};
+        use types::{Dictionary, Object, conversion::FromObject};
+
+        let hl = Highlight {
+            fg: Some(Rgb::new(0xab, 0xcd, 0xef)),
+            italic: true,
+            ..Default::default()
+        };
+
+        let obj = hl.serialize(Serializer::new()).unwrap();
+        let dict = Dictionary::from_object(obj.clone()).unwrap();
+        assert_eq!(dict.len(), 2);
+        assert_eq!(dict.get("fg"), Some(&Object::from("#abcdef")));
+
+        let deserialized = Highlight::deserialize(Deserializer::new(obj));
+        assert_eq!(deserialized.unwrap(), hl);
+    }
+}
@@ -15,6 +15,8 @@ pub struct HighlightInfos {
     pub bg_indexed: Option<bool>,
     pub blend: Option<u32>,
     pub bold: Option<bool>,
+    pub ctermbg: Option<u8>,
+    pub ctermfg: Option<u8>,
     pub default: Option<bool>,
     pub fallback: Option<bool>,
     pub fg_indexed: Option<bool>,
@@ -22,6 +24,8 @@ pub struct HighlightInfos {
     #[serde(rename = "fg")]
     pub foreground: Option<u32>,
     pub italic: Option<bool>,
+    pub link: Option<String>,
+    pub nocombine: Option<bool>,
     pub reverse: Option<bool>,
     #[serde(rename = "sp")]
     pub special: Option<u32>,
@@ -29,7 +33,10 @@ pub struct HighlightInfos {
     pub strikethrough: Option<bool>,
     pub undercurl: Option<bool>,
     pub underdash: Option<bool>,
+    pub underdashed: Option<bool>,
     pub underdot: Option<bool>,
+    pub underdotted: Option<bool>,
+    pub underdouble: Option<bool>,
     pub underline: Option<bool>,
     pub underlineline: Option<bool>,
 }
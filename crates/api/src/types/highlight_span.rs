@@ -0,0 +1,50 @@
+use super::{ByteCol, Row0};
+
+/// A highlighted range of a buffer, given to a
+/// [`HighlightLayer`](crate::HighlightLayer).
+///
+/// Both positions are 0-indexed, and the end position is exclusive.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct HighlightSpan {
+    /// The start of the highlighted range.
+    pub start: (Row0, ByteCol),
+
+    /// The end of the highlighted range.
+    pub end: (Row0, ByteCol),
+
+    /// The name of the highlight group.
+    pub hl_group: String,
+
+    /// The priority of the highlight, [`DEFAULT_PRIORITY`] unless
+    /// changed with [`with_priority`](HighlightSpan::with_priority).
+    ///
+    /// [`DEFAULT_PRIORITY`]: HighlightSpan::DEFAULT_PRIORITY
+    pub priority: u32,
+}
+
+impl HighlightSpan {
+    /// The priority Neovim gives to extmarks by default.
+    pub const DEFAULT_PRIORITY: u32 = 4096;
+
+    /// Creates a new span highlighting the given range.
+    #[inline]
+    pub fn new(
+        start: (Row0, ByteCol),
+        end: (Row0, ByteCol),
+        hl_group: impl Into<String>,
+    ) -> Self {
+        Self {
+            start,
+            end,
+            hl_group: hl_group.into(),
+            priority: Self::DEFAULT_PRIORITY,
+        }
+    }
+
+    /// Sets the priority of the span.
+    #[inline]
+    pub fn with_priority(mut self, priority: u32) -> Self {
+        self.priority = priority;
+        self
+    }
+}
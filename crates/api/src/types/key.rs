@@ -0,0 +1,694 @@
+use core::fmt;
+use core::ops::{BitOr, BitOrAssign};
+use core::str::FromStr;
+
+use crate::Error;
+
+/// A single key, optionally combined with one or more modifiers, that can be
+/// rendered to and parsed from Neovim's [key notation][1] (e.g. `<C-w>`,
+/// `<Esc>`, `<M-S-F5>`).
+///
+/// # Examples
+///
+/// ```
+/// use nvim_oxi_api::types::{Key, KeyCode, KeyModifiers};
+///
+/// assert_eq!(Key::ctrl('w').to_string(), "<C-w>");
+/// assert_eq!(Key::ESC.to_string(), "<Esc>");
+/// assert_eq!(
+///     "<C-S-F5>".parse::<Key>().unwrap(),
+///     Key::new(KeyCode::F(5), KeyModifiers::CTRL | KeyModifiers::SHIFT)
+/// );
+/// ```
+///
+/// [1]: https://neovim.io/doc/user/intro.html#key-notation
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+/// The key pressed in a [`Key`], without modifiers.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum KeyCode {
+    /// A character key. Spaces, `<`, `|` and `\` are rendered as `<Space>`,
+    /// `<lt>`, `<Bar>` and `<Bslash>`, respectively.
+    Char(char),
+
+    /// `<Nul>`.
+    Nul,
+
+    /// `<BS>`.
+    Backspace,
+
+    /// `<Tab>`.
+    Tab,
+
+    /// `<NL>`.
+    NewLine,
+
+    /// `<CR>`, also known as `<Enter>` and `<Return>`.
+    Enter,
+
+    /// `<Esc>`.
+    Esc,
+
+    /// `<Del>`.
+    Del,
+
+    /// `<Up>`.
+    Up,
+
+    /// `<Down>`.
+    Down,
+
+    /// `<Left>`.
+    Left,
+
+    /// `<Right>`.
+    Right,
+
+    /// `<Home>`.
+    Home,
+
+    /// `<End>`.
+    End,
+
+    /// `<PageUp>`.
+    PageUp,
+
+    /// `<PageDown>`.
+    PageDown,
+
+    /// `<Insert>`.
+    Insert,
+
+    /// `<Help>`.
+    Help,
+
+    /// `<Undo>`.
+    Undo,
+
+    /// A function key, from `<F1>` to `<F37>`.
+    F(u8),
+
+    /// `<Leader>`.
+    Leader,
+
+    /// `<LocalLeader>`.
+    LocalLeader,
+
+    /// `<Nop>`.
+    Nop,
+
+    /// `<Plug>`.
+    Plug,
+
+    /// `<Cmd>`.
+    Cmd,
+
+    /// `<SID>`.
+    Sid,
+
+    /// `<Ignore>`.
+    Ignore,
+
+    /// `<LeftMouse>`.
+    LeftMouse,
+
+    /// `<MiddleMouse>`.
+    MiddleMouse,
+
+    /// `<RightMouse>`.
+    RightMouse,
+
+    /// `<ScrollWheelUp>`.
+    ScrollWheelUp,
+
+    /// `<ScrollWheelDown>`.
+    ScrollWheelDown,
+
+    /// `<ScrollWheelLeft>`.
+    ScrollWheelLeft,
+
+    /// `<ScrollWheelRight>`.
+    ScrollWheelRight,
+}
+
+/// A set of modifiers held while pressing a [`Key`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct KeyModifiers(u8);
+
+/// A sequence of [`Key`]s, e.g. `<C-w>l`.
+///
+/// Parsing a sequence never fails: like Neovim does, a `<` that doesn't start
+/// a valid key name is treated as a literal `<` character.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct KeySequence(Vec<Key>);
+
+// Named keys and their names, in the order in which they're displayed. If a
+// key has more than one name, the first one is used when rendering.
+const NAMED_KEYS: &[(&str, KeyCode)] = &[
+    ("Nul", KeyCode::Nul),
+    ("BS", KeyCode::Backspace),
+    ("Tab", KeyCode::Tab),
+    ("NL", KeyCode::NewLine),
+    ("CR", KeyCode::Enter),
+    ("Enter", KeyCode::Enter),
+    ("Return", KeyCode::Enter),
+    ("Esc", KeyCode::Esc),
+    ("Del", KeyCode::Del),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+    ("Insert", KeyCode::Insert),
+    ("Help", KeyCode::Help),
+    ("Undo", KeyCode::Undo),
+    ("Leader", KeyCode::Leader),
+    ("LocalLeader", KeyCode::LocalLeader),
+    ("Nop", KeyCode::Nop),
+    ("Plug", KeyCode::Plug),
+    ("Cmd", KeyCode::Cmd),
+    ("SID", KeyCode::Sid),
+    ("Ignore", KeyCode::Ignore),
+    ("LeftMouse", KeyCode::LeftMouse),
+    ("MiddleMouse", KeyCode::MiddleMouse),
+    ("RightMouse", KeyCode::RightMouse),
+    ("ScrollWheelUp", KeyCode::ScrollWheelUp),
+    ("ScrollWheelDown", KeyCode::ScrollWheelDown),
+    ("ScrollWheelLeft", KeyCode::ScrollWheelLeft),
+    ("ScrollWheelRight", KeyCode::ScrollWheelRight),
+];
+
+// Characters that have to be written using their name.
+const NAMED_CHARS: &[(&str, char)] =
+    &[("Space", ' '), ("lt", '<'), ("Bar", '|'), ("Bslash", '\\')];
+
+// Modifiers and their prefixes, in the order in which they're displayed.
+const MODIFIERS: &[(char, KeyModifiers)] = &[
+    ('C', KeyModifiers::CTRL),
+    ('S', KeyModifiers::SHIFT),
+    ('M', KeyModifiers::ALT),
+    ('A', KeyModifiers::ALT),
+    ('D', KeyModifiers::SUPER),
+];
+
+macro_rules! named_keys {
+    ($($const:ident => $code:expr),* $(,)?) => {
+        $(
+            #[doc = concat!("Shorthand for `Key::from(", stringify!($code), ")`.")]
+            pub const $const: Self = Self::new($code, KeyModifiers::NONE);
+        )*
+    };
+}
+
+impl Key {
+    named_keys! {
+        BACKSPACE => KeyCode::Backspace,
+        CR => KeyCode::Enter,
+        DEL => KeyCode::Del,
+        DOWN => KeyCode::Down,
+        END => KeyCode::End,
+        ESC => KeyCode::Esc,
+        HOME => KeyCode::Home,
+        LEADER => KeyCode::Leader,
+        LEFT => KeyCode::Left,
+        LOCAL_LEADER => KeyCode::LocalLeader,
+        NOP => KeyCode::Nop,
+        PAGE_DOWN => KeyCode::PageDown,
+        PAGE_UP => KeyCode::PageUp,
+        PLUG => KeyCode::Plug,
+        RIGHT => KeyCode::Right,
+        SPACE => KeyCode::Char(' '),
+        TAB => KeyCode::Tab,
+        UP => KeyCode::Up,
+    }
+
+    /// Creates a new key from a key code and a set of modifiers.
+    #[inline]
+    pub const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    /// Returns the `<M-{ch}>` key.
+    #[inline]
+    pub fn alt(ch: char) -> Self {
+        Self::from(ch).with_modifiers(KeyModifiers::ALT)
+    }
+
+    /// Returns the key code.
+    #[inline]
+    pub const fn code(&self) -> KeyCode {
+        self.code
+    }
+
+    /// Returns the `<C-{ch}>` key.
+    #[inline]
+    pub fn ctrl(ch: char) -> Self {
+        Self::from(ch).with_modifiers(KeyModifiers::CTRL)
+    }
+
+    /// Returns the modifiers held while pressing the key.
+    #[inline]
+    pub const fn modifiers(&self) -> KeyModifiers {
+        self.modifiers
+    }
+
+    /// Returns the `<S-{code}>` key.
+    #[inline]
+    pub fn shift(code: impl Into<KeyCode>) -> Self {
+        Self::new(code.into(), KeyModifiers::SHIFT)
+    }
+
+    /// Returns the same key with the given modifiers added to the ones it
+    /// already had.
+    #[inline]
+    pub fn with_modifiers(mut self, modifiers: KeyModifiers) -> Self {
+        self.modifiers |= modifiers;
+        self
+    }
+
+    /// Parses a single key from the start of `str`, returning it together
+    /// with the number of bytes it spans.
+    fn parse_prefix(str: &str) -> Option<(Self, usize)> {
+        let first = str.chars().next()?;
+
+        if first != '<' {
+            return Some((Self::from(first), first.len_utf8()));
+        }
+
+        let literal_lt = Some((Self::from('<'), 1));
+
+        let inner = &str[1..];
+
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = inner;
+
+        // Consume the `X-` modifier prefixes, making sure to leave something
+        // for the key itself (e.g. `<C-->` is `Ctrl` + `-`).
+        while let [prefix, b'-', next, ..] = rest.as_bytes() {
+            if *next == b'>' && rest.as_bytes().get(3) != Some(&b'>') {
+                break;
+            }
+            let Some(modifier) = KeyModifiers::from_prefix(*prefix as char)
+            else {
+                break;
+            };
+            modifiers |= modifier;
+            rest = &rest[2..];
+        }
+
+        let Some(key_char) = rest.chars().next() else { return literal_lt };
+
+        // A single character key, e.g. `<C-w>` or `<C->>`.
+        if rest[key_char.len_utf8()..].starts_with('>')
+            && !modifiers.is_empty()
+        {
+            let len = 1 + (inner.len() - rest.len()) + key_char.len_utf8() + 1;
+            let key = Self::from(key_char).with_modifiers(modifiers);
+            return Some((key, len));
+        }
+
+        let Some(name_len) = rest.find('>') else { return literal_lt };
+
+        let Some(code) = KeyCode::from_name(&rest[..name_len]) else {
+            return literal_lt;
+        };
+
+        let len = 1 + (inner.len() - rest.len()) + name_len + 1;
+
+        Some((Self::new(code, modifiers), len))
+    }
+}
+
+impl KeyCode {
+    /// Returns the key code with the given name (case-insensitive), e.g.
+    /// `"Esc"`, `"lt"` or `"F12"`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        if let Some(&(_, code)) =
+            NAMED_KEYS.iter().find(|(n, _)| n.eq_ignore_ascii_case(name))
+        {
+            return Some(code);
+        }
+
+        if let Some(&(_, ch)) =
+            NAMED_CHARS.iter().find(|(n, _)| n.eq_ignore_ascii_case(name))
+        {
+            return Some(Self::Char(ch));
+        }
+
+        if let Some(num) = name.strip_prefix(['F', 'f']) {
+            return num
+                .parse::<u8>()
+                .ok()
+                .filter(|n| (1..=37).contains(n))
+                .map(Self::F);
+        }
+
+        None
+    }
+
+    /// Returns the name used to render the key code inside `<>`, or `None`
+    /// if it's a character that can be rendered as is.
+    fn name(&self) -> Option<String> {
+        match self {
+            Self::Char(ch) => NAMED_CHARS
+                .iter()
+                .find(|(_, c)| c == ch)
+                .map(|(name, _)| (*name).to_owned()),
+            Self::F(n) => Some(format!("F{n}")),
+            other => NAMED_KEYS
+                .iter()
+                .find(|(_, code)| code == other)
+                .map(|(name, _)| (*name).to_owned()),
+        }
+    }
+}
+
+impl KeyModifiers {
+    /// No modifiers.
+    pub const NONE: Self = Self(0);
+
+    /// The `Ctrl` modifier, rendered as `C-`.
+    pub const CTRL: Self = Self(1 << 0);
+
+    /// The `Shift` modifier, rendered as `S-`.
+    pub const SHIFT: Self = Self(1 << 1);
+
+    /// The `Alt`/`Meta` modifier, rendered as `M-`.
+    pub const ALT: Self = Self(1 << 2);
+
+    /// The `Super`/`Cmd` modifier, rendered as `D-`.
+    pub const SUPER: Self = Self(1 << 3);
+
+    /// Returns whether all the modifiers in `other` are also in `self`.
+    #[inline]
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns whether there are no modifiers.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    #[inline]
+    fn from_prefix(prefix: char) -> Option<Self> {
+        MODIFIERS
+            .iter()
+            .find(|(p, _)| p.eq_ignore_ascii_case(&prefix))
+            .map(|&(_, modifier)| modifier)
+    }
+}
+
+impl KeySequence {
+    /// Creates a new, empty key sequence.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a sequence of keys written in key notation.
+    pub fn parse(mut str: &str) -> Self {
+        let mut keys = Vec::new();
+        while let Some((key, len)) = Key::parse_prefix(str) {
+            keys.push(key);
+            str = &str[len..];
+        }
+        Self(keys)
+    }
+
+    /// Appends a key to the end of the sequence.
+    #[inline]
+    pub fn push(&mut self, key: impl Into<Key>) {
+        self.0.push(key.into());
+    }
+
+    /// Appends a key to the end of the sequence, returning the sequence.
+    #[inline]
+    pub fn then(mut self, key: impl Into<Key>) -> Self {
+        self.push(key);
+        self
+    }
+
+    /// Returns the keys in the sequence.
+    #[inline]
+    pub fn keys(&self) -> &[Key] {
+        &self.0
+    }
+
+    /// Returns the sequence translated to Neovim's internal representation of
+    /// keycodes, which can be passed to [`feedkeys`](crate::feedkeys).
+    #[inline]
+    pub fn to_termcodes(&self) -> types::String {
+        crate::replace_termcodes(self.to_string(), true, false, true)
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.code.name();
+
+        if self.modifiers.is_empty() {
+            return match (name, self.code) {
+                (Some(name), _) => write!(f, "<{name}>"),
+                (None, KeyCode::Char(ch)) => write!(f, "{ch}"),
+                (None, _) => unreachable!("all other codes have a name"),
+            };
+        }
+
+        f.write_str("<")?;
+
+        for &(prefix, modifier) in MODIFIERS {
+            // `A-` is an alias of `M-`.
+            if prefix != 'A' && self.modifiers.contains(modifier) {
+                write!(f, "{prefix}-")?;
+            }
+        }
+
+        match (name, self.code) {
+            (Some(name), _) => write!(f, "{name}>"),
+            (None, KeyCode::Char(ch)) => write!(f, "{ch}>"),
+            (None, _) => unreachable!("all other codes have a name"),
+        }
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|key| fmt::Display::fmt(key, f))
+    }
+}
+
+impl From<char> for KeyCode {
+    #[inline]
+    fn from(ch: char) -> Self {
+        match ch {
+            '\0' => Self::Nul,
+            '\t' => Self::Tab,
+            '\n' => Self::NewLine,
+            '\r' => Self::Enter,
+            '\x1b' => Self::Esc,
+            other => Self::Char(other),
+        }
+    }
+}
+
+impl From<char> for Key {
+    #[inline]
+    fn from(ch: char) -> Self {
+        KeyCode::from(ch).into()
+    }
+}
+
+impl From<KeyCode> for Key {
+    #[inline]
+    fn from(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+}
+
+impl From<Key> for KeySequence {
+    #[inline]
+    fn from(key: Key) -> Self {
+        Self(vec![key])
+    }
+}
+
+impl From<&str> for KeySequence {
+    #[inline]
+    fn from(str: &str) -> Self {
+        Self::parse(str)
+    }
+}
+
+impl<K: Into<Key>> FromIterator<K> for KeySequence {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        Self(iter.into_iter().map(Into::into).collect())
+    }
+}
+
+impl IntoIterator for KeySequence {
+    type Item = Key;
+    type IntoIter = std::vec::IntoIter<Key>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl FromStr for Key {
+    type Err = Error;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match Key::parse_prefix(str) {
+            Some((key, len)) if len == str.len() => Ok(key),
+            _ => Err(Error::custom(format!("invalid key notation: {str:?}"))),
+        }
+    }
+}
+
+impl FromStr for KeySequence {
+    type Err = core::convert::Infallible;
+
+    #[inline]
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        Ok(Self::parse(str))
+    }
+}
+
+impl From<Key> for String {
+    #[inline]
+    fn from(key: Key) -> Self {
+        key.to_string()
+    }
+}
+
+impl From<KeySequence> for String {
+    #[inline]
+    fn from(keys: KeySequence) -> Self {
+        keys.to_string()
+    }
+}
+
+impl From<Key> for types::String {
+    #[inline]
+    fn from(key: Key) -> Self {
+        key.to_string().into()
+    }
+}
+
+impl From<&KeySequence> for types::String {
+    #[inline]
+    fn from(keys: &KeySequence) -> Self {
+        keys.to_string().into()
+    }
+}
+
+impl From<KeySequence> for types::String {
+    #[inline]
+    fn from(keys: KeySequence) -> Self {
+        (&keys).into()
+    }
+}
+
+impl BitOr for KeyModifiers {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for KeyModifiers {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_keys() {
+        assert_eq!("a", Key::from('a').to_string());
+        assert_eq!("<C-w>", Key::ctrl('w').to_string());
+        assert_eq!("<M-x>", Key::alt('x').to_string());
+        assert_eq!("<S-Tab>", Key::shift(KeyCode::Tab).to_string());
+        assert_eq!("<Esc>", Key::ESC.to_string());
+        assert_eq!("<Space>", Key::from(' ').to_string());
+        assert_eq!("<lt>", Key::from('<').to_string());
+        assert_eq!("<F12>", Key::from(KeyCode::F(12)).to_string());
+        assert_eq!(
+            "<C-S-M-D-Left>",
+            Key::new(
+                KeyCode::Left,
+                KeyModifiers::CTRL
+                    | KeyModifiers::SHIFT
+                    | KeyModifiers::ALT
+                    | KeyModifiers::SUPER
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn parse_keys() {
+        assert_eq!(Ok(Key::ctrl('w')), "<C-w>".parse());
+        assert_eq!(Ok(Key::ctrl('w')), "<c-w>".parse());
+        assert_eq!(Ok(Key::ESC), "<esc>".parse());
+        assert_eq!(Ok(Key::CR), "<Enter>".parse());
+        assert_eq!(Ok(Key::alt('x')), "<A-x>".parse());
+        assert_eq!(Ok(Key::ctrl('-')), "<C-->".parse());
+        assert_eq!(Ok(Key::ctrl('>')), "<C->>".parse());
+        assert_eq!(Ok(Key::from('<')), "<lt>".parse());
+        assert_eq!(Ok(Key::from(KeyCode::F(5))), "<F5>".parse());
+        assert!("<F38>".parse::<Key>().is_err());
+        assert!("ab".parse::<Key>().is_err());
+    }
+
+    #[test]
+    fn parse_sequences() {
+        let keys = KeySequence::parse("<C-w>l");
+        assert_eq!(keys.keys(), &[Key::ctrl('w'), Key::from('l')]);
+
+        let keys = KeySequence::parse("<foo>");
+        assert_eq!(5, keys.keys().len());
+        assert_eq!(Key::from('<'), keys.keys()[0]);
+
+        let keys = KeySequence::parse("<");
+        assert_eq!(keys.keys(), &[Key::from('<')]);
+
+        let keys = KeySequence::parse("<C-");
+        assert_eq!(3, keys.keys().len());
+    }
+
+    #[test]
+    fn roundtrip() {
+        for notation in [
+            "<C-w>l",
+            "<Leader>ff",
+            "i<Space>foo<Esc>",
+            "<S-Tab><M-CR>",
+            "<lt>Bar><Bar>",
+            "<Plug>(foo)",
+            "ユ<D-z>",
+        ] {
+            let keys = KeySequence::parse(notation);
+            assert_eq!(notation, keys.to_string());
+            assert_eq!(keys, KeySequence::parse(&keys.to_string()));
+        }
+    }
+}
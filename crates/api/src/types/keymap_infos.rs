@@ -19,7 +19,11 @@ pub struct KeymapInfos {
     pub buffer: Option<Buffer>,
 
     /// Optional callback triggered by the keymap.
-    pub callback: Option<Function<(), ()>>,
+    pub callback: Option<KeymapCallback>,
+
+    /// The description of the keymap, if any.
+    #[serde(default)]
+    pub desc: Option<String>,
 
     /// Whether the keymap argument is an expression.
     #[serde(deserialize_with = "utils::bool_from_int")]
@@ -61,8 +65,49 @@ pub struct KeymapInfos {
     pub silent: bool,
 }
 
+/// The callback triggered by a keymap.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum KeymapCallback {
+    /// The callback of a regular mapping.
+    Normal(Function<(), ()>),
+
+    /// The callback of an [`expr`](KeymapInfos::expr) mapping, which returns
+    /// the keys to be fed to Neovim.
+    Expr(Function<(), String>),
+}
+
+impl KeymapCallback {
+    /// Returns the callback's Lua function, regardless of its return type.
+    #[inline]
+    pub fn as_function(&self) -> Function<(), ()> {
+        match self {
+            Self::Normal(fun) => fun.clone(),
+            Self::Expr(fun) => crate::utils::cast_function(fun.clone()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for KeymapCallback {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Function::deserialize(deserializer).map(Self::Normal)
+    }
+}
+
 impl FromObject for KeymapInfos {
     fn from_object(obj: Object) -> Result<Self, conversion::Error> {
-        Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
+        let mut infos = Self::deserialize(Deserializer::new(obj))?;
+
+        // The callback of an `expr` mapping returns the keys to feed.
+        if infos.expr
+            && let Some(KeymapCallback::Normal(fun)) = infos.callback.take()
+        {
+            let fun = crate::utils::cast_function(fun);
+            infos.callback = Some(KeymapCallback::Expr(fun));
+        }
+
+        Ok(infos)
     }
 }
@@ -0,0 +1,60 @@
+use crate::{Buffer, Window};
+
+/// The layout of the split windows in a tabpage, returned by
+/// [`TabPage::layout`](crate::TabPage::layout).
+///
+/// Floating windows are not part of the layout.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Layout {
+    /// Windows or layouts placed side by side, from left to right.
+    Row(Vec<Layout>),
+
+    /// Windows or layouts stacked on top of each other, from top to bottom.
+    Col(Vec<Layout>),
+
+    /// A single window.
+    Leaf {
+        /// The window.
+        window: Window,
+
+        /// The buffer shown in the window.
+        buffer: Buffer,
+
+        /// The width of the window, in columns.
+        width: u32,
+
+        /// The height of the window, in rows.
+        height: u32,
+    },
+}
+
+impl Layout {
+    /// Returns an iterator over the windows in the layout, from the top left
+    /// to the bottom right.
+    pub fn windows(&self) -> impl Iterator<Item = &Window> + '_ {
+        let mut stack = vec![self];
+
+        core::iter::from_fn(move || {
+            while let Some(layout) = stack.pop() {
+                match layout {
+                    Self::Row(children) | Self::Col(children) => {
+                        stack.extend(children.iter().rev())
+                    },
+                    Self::Leaf { window, .. } => return Some(window),
+                }
+            }
+            None
+        })
+    }
+
+    /// Returns the buffer of the top left window in the layout, or `None` if
+    /// the layout has no windows.
+    pub(crate) fn first_buffer(&self) -> Option<&Buffer> {
+        match self {
+            Self::Row(children) | Self::Col(children) => {
+                children.first().and_then(Self::first_buffer)
+            },
+            Self::Leaf { buffer, .. } => Some(buffer),
+        }
+    }
+}
@@ -2,6 +2,8 @@
 
 mod autocmd_callback_args;
 mod autocmd_infos;
+mod buf_event;
+mod byte_col;
 mod channel_infos;
 mod client_infos;
 mod cmd_infos;
@@ -26,24 +28,38 @@ mod extmark_virt_text_position;
 mod get_extmarks_namespace_id;
 mod get_hl_infos;
 mod got_mode;
+mod gravity;
+mod highlight;
 mod highlight_infos;
+mod highlight_span;
+mod key;
 mod keymap_infos;
+mod layout;
 mod log_level;
 mod mode;
+mod motion_type;
 mod mouse_action;
 mod mouse_button;
 mod one_or_more;
+mod operator_range;
 mod option_infos;
 mod parsed_viml_expression;
 mod paste_phase;
+mod position;
+mod preview_mode;
 mod proc_infos;
 #[cfg(feature = "neovim-0-12")] // On 0.12 and Nightly.
 mod progress_message_status;
+mod qf_item;
+mod qf_list_infos;
 mod register_type;
+mod rgb;
+mod row;
 mod split_direction;
 mod split_modifier;
 mod statusline_highlight_infos;
 mod statusline_infos;
+mod text_edit;
 mod ui_infos;
 mod viml_ast_node;
 mod virt_lines_overflow;
@@ -59,6 +75,8 @@ mod window_title_position;
 
 pub use autocmd_callback_args::*;
 pub use autocmd_infos::*;
+pub use buf_event::*;
+pub use byte_col::*;
 pub use channel_infos::*;
 pub use client_infos::*;
 pub use cmd_infos::*;
@@ -83,24 +101,38 @@ pub use extmark_virt_text_position::*;
 pub use get_extmarks_namespace_id::GetExtmarksNamespaceId;
 pub use get_hl_infos::GetHlInfos;
 pub use got_mode::*;
+pub use gravity::*;
+pub use highlight::*;
 pub use highlight_infos::*;
+pub use highlight_span::*;
+pub use key::*;
 pub use keymap_infos::*;
+pub use layout::*;
 pub use log_level::*;
 pub use mode::*;
+pub use motion_type::*;
 pub use mouse_action::*;
 pub use mouse_button::*;
 pub use one_or_more::*;
+pub use operator_range::*;
 pub use option_infos::*;
 pub use parsed_viml_expression::*;
 pub use paste_phase::*;
+pub use position::*;
+pub use preview_mode::*;
 pub use proc_infos::*;
 #[cfg(feature = "neovim-0-12")] // On 0.12 and Nightly.
 pub use progress_message_status::ProgressMessageStatus;
+pub use qf_item::*;
+pub use qf_list_infos::*;
 pub use register_type::*;
+pub use rgb::*;
+pub use row::*;
 pub use split_direction::*;
 pub use split_modifier::*;
 pub use statusline_highlight_infos::*;
 pub use statusline_infos::*;
+pub use text_edit::*;
 pub use ui_infos::*;
 pub use viml_ast_node::*;
 pub use virt_lines_overflow::VirtLinesOverflow;
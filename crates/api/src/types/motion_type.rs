@@ -0,0 +1,42 @@
+use luajit::{Poppable, ffi::State};
+
+/// The type of motion passed to an `operatorfunc`, see
+/// [`operator`](crate::operator) for more infos.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum MotionType {
+    /// The motion was characterwise.
+    Char,
+
+    /// The motion was linewise.
+    Line,
+
+    /// The motion was blockwise.
+    Block,
+}
+
+impl MotionType {
+    /// Returns the string Neovim uses to represent the motion type.
+    #[inline]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Char => "char",
+            Self::Line => "line",
+            Self::Block => "block",
+        }
+    }
+}
+
+impl Poppable for MotionType {
+    unsafe fn pop(lstate: *mut State) -> Result<Self, luajit::Error> {
+        match <String as Poppable>::pop(lstate)?.as_str() {
+            "char" => Ok(Self::Char),
+            "line" => Ok(Self::Line),
+            "block" => Ok(Self::Block),
+            other => Err(luajit::Error::pop_error(
+                std::any::type_name::<Self>(),
+                format!("unknown motion type {other:?}"),
+            )),
+        }
+    }
+}
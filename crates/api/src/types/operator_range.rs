@@ -0,0 +1,15 @@
+use super::{ByteCol, Row1};
+
+/// The region of text an operator acts on, i.e. the text between the `'[`
+/// and `']` marks. See [`operator`](crate::operator) for more infos.
+///
+/// Like marks, both positions are (1,0)-indexed `(row, col)` tuples, and the
+/// end position is inclusive.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct OperatorRange {
+    /// The position of the `'[` mark.
+    pub start: (Row1, ByteCol),
+
+    /// The position of the `']` mark.
+    pub end: (Row1, ByteCol),
+}
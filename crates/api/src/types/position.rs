@@ -0,0 +1,267 @@
+use serde::{Deserialize, Serialize};
+
+use super::Row0;
+
+/// The unit in which the column of a [`Position`] is measured.
+///
+/// The Neovim API always uses byte columns, while the LSP specification
+/// defaults to UTF-16 code units.
+#[derive(
+    Copy, Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize,
+)]
+pub enum PositionEncoding {
+    /// Bytes of the UTF-8 encoded line.
+    #[default]
+    #[serde(rename = "utf-8")]
+    Utf8,
+
+    /// UTF-16 code units.
+    #[serde(rename = "utf-16")]
+    Utf16,
+
+    /// UTF-32 code units, i.e. codepoints.
+    #[serde(rename = "utf-32")]
+    Utf32,
+}
+
+/// A 0-indexed position in a buffer whose column is measured in the given
+/// [`PositionEncoding`].
+///
+/// Positions can be converted between encodings with
+/// [`Buffer::convert_position`](crate::Buffer::convert_position).
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Position {
+    /// The line of the position.
+    pub row: Row0,
+
+    /// The column of the position, measured in
+    /// [`encoding`](Position::encoding) units.
+    pub col: usize,
+
+    /// The unit of the column.
+    pub encoding: PositionEncoding,
+}
+
+impl PositionEncoding {
+    /// Returns the name of the encoding as used by Neovim and the LSP
+    /// specification, i.e. `"utf-8"`, `"utf-16"` or `"utf-32"`.
+    #[inline]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Utf8 => "utf-8",
+            Self::Utf16 => "utf-16",
+            Self::Utf32 => "utf-32",
+        }
+    }
+
+    /// Returns the length of the given text in this encoding.
+    ///
+    /// Like in Neovim, every byte that's not part of a valid UTF-8 sequence
+    /// counts as a single code unit.
+    #[inline]
+    pub fn len(self, text: &[u8]) -> usize {
+        self.index_of_byte(text, text.len()).expect("in bounds")
+    }
+
+    /// Converts a byte index into the given line to an index in this
+    /// encoding, following the semantics of [`vim.str_utfindex()`][1]: a
+    /// byte index in the middle of a character is rounded up to the end of
+    /// that character.
+    ///
+    /// Returns `None` if the index is past the end of the line.
+    ///
+    /// [1]: https://neovim.io/doc/user/lua.html#vim.str_utfindex()
+    pub fn index_of_byte(self, line: &[u8], byte_idx: usize) -> Option<usize> {
+        if byte_idx > line.len() {
+            return None;
+        }
+
+        if self == Self::Utf8 {
+            return Some(byte_idx);
+        }
+
+        Some(
+            chars(line, self)
+                .take_while(|&(start, _, _)| start < byte_idx)
+                .map(|(_, _, units)| units)
+                .sum(),
+        )
+    }
+
+    /// Converts an index in this encoding into the given line to a byte
+    /// index, following the semantics of [`vim.str_byteindex()`][1]: an
+    /// index in the middle of a character is rounded up to the end of that
+    /// character.
+    ///
+    /// Returns `None` if the index is past the end of the line.
+    ///
+    /// [1]: https://neovim.io/doc/user/lua.html#vim.str_byteindex()
+    pub fn byte_of_index(self, line: &[u8], idx: usize) -> Option<usize> {
+        if self == Self::Utf8 {
+            return (idx <= line.len()).then_some(idx);
+        }
+
+        let mut units = 0;
+
+        for (start, char_len, char_units) in chars(line, self) {
+            if units >= idx {
+                return Some(start);
+            }
+            units += char_units;
+            if units >= idx {
+                return Some(start + char_len);
+            }
+        }
+
+        (units >= idx).then_some(line.len())
+    }
+}
+
+impl Position {
+    /// Creates a new position.
+    #[inline]
+    pub fn new(row: Row0, col: usize, encoding: PositionEncoding) -> Self {
+        Self { row, col, encoding }
+    }
+
+    /// Creates a new position whose column is a byte index.
+    #[inline]
+    pub fn byte(row: Row0, col: usize) -> Self {
+        Self::new(row, col, PositionEncoding::Utf8)
+    }
+
+    /// Creates a new position whose column is in UTF-16 code units.
+    #[inline]
+    pub fn utf16(row: Row0, col: usize) -> Self {
+        Self::new(row, col, PositionEncoding::Utf16)
+    }
+
+    /// Creates a new position whose column is in UTF-32 code units.
+    #[inline]
+    pub fn utf32(row: Row0, col: usize) -> Self {
+        Self::new(row, col, PositionEncoding::Utf32)
+    }
+
+    /// Converts the position to another encoding, given the content of its
+    /// line.
+    ///
+    /// Returns `None` if the column is past the end of the line.
+    #[inline]
+    pub fn to_encoding(
+        self,
+        line: &[u8],
+        encoding: PositionEncoding,
+    ) -> Option<Self> {
+        let byte_idx = self.encoding.byte_of_index(line, self.col)?;
+        let col = encoding.index_of_byte(line, byte_idx)?;
+        Some(Self::new(self.row, col, encoding))
+    }
+}
+
+/// Returns an iterator over the `(start, byte_len, units)` of every
+/// character in the line, where `units` is its length in the given
+/// encoding.
+fn chars(
+    line: &[u8],
+    encoding: PositionEncoding,
+) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+    let mut start = 0;
+
+    core::iter::from_fn(move || {
+        if start == line.len() {
+            return None;
+        }
+
+        let char_len = utf8_char_len(&line[start..]);
+
+        let units = match encoding {
+            PositionEncoding::Utf8 => char_len,
+            PositionEncoding::Utf16 if char_len == 4 => 2,
+            PositionEncoding::Utf16 | PositionEncoding::Utf32 => 1,
+        };
+
+        let item = (start, char_len, units);
+        start += char_len;
+        Some(item)
+    })
+}
+
+/// Returns the length of the UTF-8 sequence at the start of the given bytes,
+/// or `1` if it's not valid.
+fn utf8_char_len(bytes: &[u8]) -> usize {
+    let len = match bytes[0] {
+        0x00..=0x7F => 1,
+        0xC2..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF4 => 4,
+        _ => return 1,
+    };
+
+    match bytes.get(..len).map(core::str::from_utf8) {
+        Some(Ok(_)) => len,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // "a" is 1 byte, "é" is 2, "€" is 3 and "😀" is 4 (2 UTF-16 units).
+    const LINE: &[u8] = "aé€😀b".as_bytes();
+
+    #[test]
+    fn len() {
+        assert_eq!(PositionEncoding::Utf8.len(LINE), 11);
+        assert_eq!(PositionEncoding::Utf16.len(LINE), 6);
+        assert_eq!(PositionEncoding::Utf32.len(LINE), 5);
+    }
+
+    #[test]
+    fn index_of_byte() {
+        let utf16 = PositionEncoding::Utf16;
+        assert_eq!(utf16.index_of_byte(LINE, 0), Some(0));
+        assert_eq!(utf16.index_of_byte(LINE, 3), Some(2));
+        assert_eq!(utf16.index_of_byte(LINE, 6), Some(3));
+        assert_eq!(utf16.index_of_byte(LINE, 10), Some(5));
+        assert_eq!(utf16.index_of_byte(LINE, 12), None);
+
+        // Rounded up to the end of "€".
+        assert_eq!(utf16.index_of_byte(LINE, 4), Some(3));
+    }
+
+    #[test]
+    fn byte_of_index() {
+        let utf16 = PositionEncoding::Utf16;
+        assert_eq!(utf16.byte_of_index(LINE, 3), Some(6));
+        assert_eq!(utf16.byte_of_index(LINE, 5), Some(10));
+        assert_eq!(utf16.byte_of_index(LINE, 6), Some(11));
+        assert_eq!(utf16.byte_of_index(LINE, 7), None);
+
+        // Rounded up to the end of the surrogate pair.
+        assert_eq!(utf16.byte_of_index(LINE, 4), Some(10));
+
+        let utf32 = PositionEncoding::Utf32;
+        assert_eq!(utf32.byte_of_index(LINE, 4), Some(10));
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let line = b"a\xFFb";
+        assert_eq!(PositionEncoding::Utf16.len(line), 3);
+        assert_eq!(PositionEncoding::Utf32.byte_of_index(line, 2), Some(2));
+    }
+
+    #[test]
+    fn to_encoding() {
+        let pos = Position::utf16(Row0(3), 5);
+        assert_eq!(
+            pos.to_encoding(LINE, PositionEncoding::Utf8),
+            Some(Position::byte(Row0(3), 10))
+        );
+        assert_eq!(
+            pos.to_encoding(LINE, PositionEncoding::Utf32),
+            Some(Position::utf32(Row0(3), 4))
+        );
+    }
+}
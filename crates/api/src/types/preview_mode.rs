@@ -0,0 +1,30 @@
+use core::ffi::c_int;
+
+use luajit::{Pushable, ffi::State};
+use types::Integer;
+
+/// Value returned by the command preview callback registered with
+/// [`CreateCommandOptsBuilder::preview`](crate::opts::CreateCommandOptsBuilder::preview)
+/// to tell Neovim how to display the preview. See `:h :command-preview` for
+/// more infos.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum PreviewMode {
+    /// Don't show a preview.
+    #[default]
+    NoPreview = 0,
+
+    /// Show the preview without opening the preview window.
+    Preview = 1,
+
+    /// Show the preview and open the preview window, if
+    /// [`inccommand`](https://neovim.io/doc/user/options.html#'inccommand')
+    /// is set to `split`.
+    PreviewWithSplit = 2,
+}
+
+impl Pushable for PreviewMode {
+    unsafe fn push(self, lstate: *mut State) -> Result<c_int, luajit::Error> {
+        (self as Integer).push(lstate)
+    }
+}
@@ -0,0 +1,264 @@
+use core::fmt;
+
+use serde::{Deserialize, de};
+use types::{
+    Dictionary,
+    Integer,
+    Object,
+    conversion::{self, FromObject},
+    serde::Deserializer,
+};
+
+use super::Row1;
+use crate::{Buffer, serde_utils as utils};
+
+/// An entry of a quickfix or location list. See `:h setqflist-what` for more
+/// infos.
+///
+/// Either [`bufnr`](QfItem::bufnr) or [`filename`](QfItem::filename) should
+/// be set for the item to refer to a location. Column numbers are 1-indexed,
+/// with `0` meaning "not set".
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+pub struct QfItem {
+    /// The buffer the item refers to.
+    #[serde(default, deserialize_with = "utils::zero_is_none")]
+    pub bufnr: Option<Buffer>,
+
+    /// The name of the file the item refers to. Only used when setting a
+    /// list, as Neovim always returns a [`bufnr`](QfItem::bufnr) instead.
+    #[serde(default)]
+    pub filename: Option<String>,
+
+    /// The module name, displayed instead of the file name if set.
+    #[serde(default, deserialize_with = "utils::empty_string_is_none")]
+    pub module: Option<String>,
+
+    /// The line number of the item.
+    #[serde(default, deserialize_with = "utils::zero_is_none")]
+    pub lnum: Option<Row1>,
+
+    /// The end line number of the item, if it spans multiple lines.
+    #[serde(default, deserialize_with = "utils::zero_is_none")]
+    pub end_lnum: Option<Row1>,
+
+    /// The column number of the item.
+    #[serde(default)]
+    pub col: usize,
+
+    /// The end column number of the item, if it spans multiple columns.
+    #[serde(default, deserialize_with = "utils::zero_is_none")]
+    pub end_col: Option<usize>,
+
+    /// Whether [`col`](QfItem::col) is a screen column instead of a byte
+    /// index.
+    #[serde(default, deserialize_with = "utils::bool_from_int")]
+    pub vcol: bool,
+
+    /// The error number.
+    #[serde(default, deserialize_with = "utils::zero_is_none")]
+    pub nr: Option<u32>,
+
+    /// A search pattern used to locate the item.
+    #[serde(default, deserialize_with = "utils::empty_string_is_none")]
+    pub pattern: Option<String>,
+
+    /// A description of the item.
+    #[serde(default)]
+    pub text: String,
+
+    /// The kind of the item.
+    #[serde(
+        default,
+        rename = "type",
+        deserialize_with = "utils::empty_string_is_none"
+    )]
+    pub kind: Option<QfItemKind>,
+
+    /// Whether the item is a recognized error message. Ignored when setting
+    /// a list.
+    #[serde(default, deserialize_with = "utils::bool_from_int")]
+    pub valid: bool,
+
+    /// Arbitrary data attached to the item.
+    #[serde(default, deserialize_with = "user_data")]
+    pub user_data: Option<Object>,
+}
+
+/// The kind of a [`QfItem`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum QfItemKind {
+    Error,
+    Warning,
+    Info,
+    Note,
+
+    /// Any other single-character type.
+    Other(char),
+}
+
+impl QfItem {
+    /// Creates a new item pointing to the given line and column of a buffer.
+    #[inline]
+    pub fn buffer(buffer: Buffer, lnum: Row1, col: usize) -> Self {
+        Self {
+            bufnr: Some(buffer),
+            lnum: Some(lnum),
+            col,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a new item pointing to the given line and column of a file.
+    #[inline]
+    pub fn file(filename: impl Into<String>, lnum: Row1, col: usize) -> Self {
+        Self {
+            filename: Some(filename.into()),
+            lnum: Some(lnum),
+            col,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the end position of the item.
+    #[inline]
+    pub fn end(mut self, end_lnum: Row1, end_col: usize) -> Self {
+        self.end_lnum = Some(end_lnum);
+        self.end_col = Some(end_col);
+        self
+    }
+
+    /// Sets the kind of the item.
+    #[inline]
+    pub fn kind(mut self, kind: QfItemKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Sets the description of the item.
+    #[inline]
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Attaches arbitrary data to the item.
+    #[inline]
+    pub fn user_data(mut self, user_data: impl Into<Object>) -> Self {
+        self.user_data = Some(user_data.into());
+        self
+    }
+}
+
+impl QfItemKind {
+    /// Returns the character Neovim uses to represent this kind.
+    #[inline]
+    pub fn as_char(self) -> char {
+        match self {
+            Self::Error => 'E',
+            Self::Warning => 'W',
+            Self::Info => 'I',
+            Self::Note => 'N',
+            Self::Other(c) => c,
+        }
+    }
+}
+
+impl From<char> for QfItemKind {
+    #[inline]
+    fn from(c: char) -> Self {
+        match c {
+            'E' | 'e' => Self::Error,
+            'W' | 'w' => Self::Warning,
+            'I' | 'i' => Self::Info,
+            'N' | 'n' => Self::Note,
+            other => Self::Other(other),
+        }
+    }
+}
+
+impl fmt::Display for QfItemKind {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.as_char(), f)
+    }
+}
+
+impl<'de> Deserialize<'de> for QfItemKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let str = String::deserialize(deserializer)?;
+        let mut chars = str.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c.into()),
+            _ => Err(de::Error::invalid_length(
+                str.len(),
+                &"string with a single character",
+            )),
+        }
+    }
+}
+
+impl FromObject for QfItem {
+    #[inline]
+    fn from_object(obj: Object) -> Result<Self, conversion::Error> {
+        Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
+    }
+}
+
+impl From<QfItem> for Dictionary {
+    fn from(item: QfItem) -> Self {
+        let mut dict = Dictionary::new();
+        if let Some(buffer) = item.bufnr {
+            dict.insert("bufnr", buffer);
+        }
+        if let Some(filename) = item.filename {
+            dict.insert("filename", filename);
+        }
+        if let Some(module) = item.module {
+            dict.insert("module", module);
+        }
+        if let Some(lnum) = item.lnum {
+            dict.insert("lnum", lnum);
+        }
+        if let Some(end_lnum) = item.end_lnum {
+            dict.insert("end_lnum", end_lnum);
+        }
+        dict.insert("col", item.col as Integer);
+        if let Some(end_col) = item.end_col {
+            dict.insert("end_col", end_col as Integer);
+        }
+        dict.insert("vcol", item.vcol as Integer);
+        if let Some(nr) = item.nr {
+            dict.insert("nr", nr);
+        }
+        if let Some(pattern) = item.pattern {
+            dict.insert("pattern", pattern);
+        }
+        dict.insert("text", item.text);
+        if let Some(kind) = item.kind {
+            dict.insert("type", kind.as_char());
+        }
+        if let Some(user_data) = item.user_data {
+            dict.insert("user_data", user_data);
+        }
+        dict
+    }
+}
+
+impl From<QfItem> for Object {
+    #[inline]
+    fn from(item: QfItem) -> Self {
+        Dictionary::from(item).into()
+    }
+}
+
+/// Neovim returns a `nil` `user_data` as an empty string.
+fn user_data<'de, D>(deserializer: D) -> Result<Option<Object>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    let obj = Object::deserialize(deserializer)?;
+    Ok(super::qf_list_infos::non_empty(obj))
+}
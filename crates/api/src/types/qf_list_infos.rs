@@ -0,0 +1,80 @@
+use serde::{Deserialize, de};
+use types::{
+    Object,
+    ObjectKind,
+    conversion::{self, FromObject},
+    serde::Deserializer,
+};
+
+use super::QfItem;
+use crate::{Window, serde_utils as utils};
+
+/// Informations about a quickfix or location list, returned by
+/// [`Quickfix::get`](crate::Quickfix::get) and
+/// [`LocList::get`](crate::LocList::get).
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct QfListInfos {
+    /// The current value of the list's `changedtick`.
+    #[serde(default)]
+    pub changedtick: u32,
+
+    /// The context attached to the list, if any.
+    #[serde(default, deserialize_with = "context")]
+    pub context: Option<Object>,
+
+    /// The unique identifier of the list.
+    pub id: u32,
+
+    /// The index of the current entry in the list, starting from 1.
+    #[serde(default)]
+    pub idx: usize,
+
+    /// The entries of the list.
+    #[serde(default)]
+    pub items: Vec<QfItem>,
+
+    /// The position of the list in the quickfix stack.
+    #[serde(default)]
+    pub nr: u32,
+
+    /// The number of entries in the list.
+    #[serde(default)]
+    pub size: usize,
+
+    /// The title of the list.
+    #[serde(default)]
+    pub title: String,
+
+    /// The window displaying the list, if it's open.
+    #[serde(default, deserialize_with = "utils::zero_is_none")]
+    pub winid: Option<Window>,
+}
+
+impl FromObject for QfListInfos {
+    #[inline]
+    fn from_object(obj: Object) -> Result<Self, conversion::Error> {
+        Self::deserialize(Deserializer::new(obj)).map_err(Into::into)
+    }
+}
+
+/// Neovim returns an empty string for unset contexts.
+fn context<'de, D>(deserializer: D) -> Result<Option<Object>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    Object::deserialize(deserializer).map(non_empty)
+}
+
+pub(super) fn non_empty(obj: Object) -> Option<Object> {
+    match obj.kind() {
+        ObjectKind::Nil => None,
+        // SAFETY: the object is a string.
+        ObjectKind::String
+            if unsafe { obj.as_nvim_str_unchecked() }.is_empty() =>
+        {
+            None
+        },
+        _ => Some(obj),
+    }
+}
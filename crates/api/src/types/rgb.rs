@@ -0,0 +1,312 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{Error, Result};
+
+/// A 24-bit RGB color.
+///
+/// # Examples
+///
+/// ```
+/// use nvim_oxi_api::types::Rgb;
+///
+/// let red = "#ff0000".parse::<Rgb>().unwrap();
+/// assert_eq!(red, Rgb::new(255, 0, 0));
+/// assert_eq!(red.to_string(), "#ff0000");
+/// assert_eq!(u32::from(red), 0xff0000);
+///
+/// assert_eq!(red.blend(Rgb::new(0, 0, 255), 0.5), Rgb::new(128, 0, 128));
+/// assert!((Rgb::BLACK.contrast_ratio(Rgb::WHITE) - 21.0).abs() < 1e-9);
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+/// The intensities of the six levels of the 6x6x6 color cube in the xterm
+/// 256-color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The first 16 colors of the xterm 256-color palette. Terminals usually let
+/// users customize these, so they're only an approximation.
+const ANSI_COLORS: [Rgb; 16] = [
+    Rgb::new(0, 0, 0),
+    Rgb::new(205, 0, 0),
+    Rgb::new(0, 205, 0),
+    Rgb::new(205, 205, 0),
+    Rgb::new(0, 0, 238),
+    Rgb::new(205, 0, 205),
+    Rgb::new(0, 205, 205),
+    Rgb::new(229, 229, 229),
+    Rgb::new(127, 127, 127),
+    Rgb::new(255, 0, 0),
+    Rgb::new(0, 255, 0),
+    Rgb::new(255, 255, 0),
+    Rgb::new(92, 92, 255),
+    Rgb::new(255, 0, 255),
+    Rgb::new(0, 255, 255),
+    Rgb::new(255, 255, 255),
+];
+
+impl Rgb {
+    pub const BLACK: Self = Self::new(0, 0, 0);
+    pub const WHITE: Self = Self::new(255, 255, 255);
+
+    /// Creates a new color from its red, green and blue components.
+    #[inline]
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Parses a color from either a `#rrggbb` hex string, a cterm color
+    /// index (e.g. `"208"`) or the name of a color known to Neovim (e.g.
+    /// `"DarkOrange"`, see [`get_color_map`](crate::get_color_map)).
+    pub fn parse(color: &str) -> Result<Self> {
+        if color.starts_with('#') {
+            color.parse()
+        } else if let Ok(index) = color.parse::<u8>() {
+            Ok(Self::from_cterm(index))
+        } else {
+            Self::from_name(color)
+        }
+    }
+
+    /// Returns the color with the given name, using
+    /// [`get_color_by_name`](crate::get_color_by_name).
+    #[inline]
+    pub fn from_name(name: &str) -> Result<Self> {
+        crate::get_color_by_name(name).map(Self::from)
+    }
+
+    /// Returns an iterator over the names of the colors known to Neovim and
+    /// their values, using [`get_color_map`](crate::get_color_map).
+    #[inline]
+    pub fn named_colors() -> impl Iterator<Item = (String, Self)> {
+        crate::get_color_map().map(|(name, color)| (name, color.into()))
+    }
+
+    /// Returns the color at the given index of the xterm 256-color palette.
+    pub fn from_cterm(index: u8) -> Self {
+        match index {
+            0..16 => ANSI_COLORS[index as usize],
+            16..232 => {
+                let index = index - 16;
+                Self::new(
+                    CUBE_LEVELS[(index / 36) as usize],
+                    CUBE_LEVELS[(index / 6 % 6) as usize],
+                    CUBE_LEVELS[(index % 6) as usize],
+                )
+            },
+            232.. => {
+                let level = 8 + 10 * (index - 232);
+                Self::new(level, level, level)
+            },
+        }
+    }
+
+    /// Returns the index of the closest color in the xterm 256-color
+    /// palette, ignoring the first 16 colors since they depend on the
+    /// terminal's configuration.
+    pub fn to_cterm(self) -> u8 {
+        let closest_level = |component: u8| {
+            (0..CUBE_LEVELS.len())
+                .min_by_key(|&idx| component.abs_diff(CUBE_LEVELS[idx]))
+                .expect("there are six levels") as u8
+        };
+
+        let cube_index = 16
+            + 36 * closest_level(self.r)
+            + 6 * closest_level(self.g)
+            + closest_level(self.b);
+
+        let average =
+            (self.r as u16 + self.g as u16 + self.b as u16) as f64 / 3.0;
+        let gray_index =
+            232 + ((average - 8.0) / 10.0).round().clamp(0.0, 23.0) as u8;
+
+        [cube_index, gray_index]
+            .into_iter()
+            .min_by_key(|&index| self.distance(Self::from_cterm(index)))
+            .expect("there are two candidates")
+    }
+
+    /// Mixes this color with `other`. A `ratio` of `0.0` returns this color,
+    /// and a `ratio` of `1.0` returns `other`.
+    pub fn blend(self, other: Self, ratio: f64) -> Self {
+        let ratio = ratio.clamp(0.0, 1.0);
+        let mix = |from: u8, to: u8| {
+            (from as f64 + (to as f64 - from as f64) * ratio).round() as u8
+        };
+        Self::new(
+            mix(self.r, other.r),
+            mix(self.g, other.g),
+            mix(self.b, other.b),
+        )
+    }
+
+    /// Blends this color with white by the given amount, between `0.0` and
+    /// `1.0`.
+    #[inline]
+    pub fn lighten(self, amount: f64) -> Self {
+        self.blend(Self::WHITE, amount)
+    }
+
+    /// Blends this color with black by the given amount, between `0.0` and
+    /// `1.0`.
+    #[inline]
+    pub fn darken(self, amount: f64) -> Self {
+        self.blend(Self::BLACK, amount)
+    }
+
+    /// Returns the [relative luminance][1] of the color, between `0.0` for
+    /// black and `1.0` for white.
+    ///
+    /// [1]: https://www.w3.org/TR/WCAG21/#dfn-relative-luminance
+    pub fn luminance(self) -> f64 {
+        let linear = |component: u8| {
+            let c = component as f64 / 255.0;
+            if c <= 0.03928 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * linear(self.r)
+            + 0.7152 * linear(self.g)
+            + 0.0722 * linear(self.b)
+    }
+
+    /// Returns the [contrast ratio][1] between the two colors, from `1.0`
+    /// for identical luminances to `21.0` for black on white.
+    ///
+    /// [1]: https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio
+    pub fn contrast_ratio(self, other: Self) -> f64 {
+        let (a, b) = (self.luminance(), other.luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// Returns whichever of `candidates` has the highest contrast ratio with
+    /// this color, or `None` if there are no candidates.
+    pub fn most_contrasting(
+        self,
+        candidates: impl IntoIterator<Item = Self>,
+    ) -> Option<Self> {
+        candidates.into_iter().max_by(|a, b| {
+            self.contrast_ratio(*a).total_cmp(&self.contrast_ratio(*b))
+        })
+    }
+
+    /// The squared euclidean distance between the two colors.
+    fn distance(self, other: Self) -> u32 {
+        let diff = |a: u8, b: u8| (a.abs_diff(b) as u32).pow(2);
+        diff(self.r, other.r) + diff(self.g, other.g) + diff(self.b, other.b)
+    }
+}
+
+impl fmt::Display for Rgb {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+impl FromStr for Rgb {
+    type Err = Error;
+
+    /// Parses a `#rrggbb` hex string. Use [`Rgb::parse`] to also accept
+    /// color names and cterm indices.
+    fn from_str(str: &str) -> Result<Self> {
+        let invalid =
+            || Error::custom(format!("{str:?} is not a #rrggbb color"));
+
+        let hex = str.strip_prefix('#').ok_or_else(invalid)?;
+
+        if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+
+        u32::from_str_radix(hex, 16).map(Self::from).map_err(|_| invalid())
+    }
+}
+
+impl serde::Serialize for Rgb {
+    /// Serializes the color as a `#rrggbb` hex string.
+    #[inline]
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Rgb {
+    /// Deserializes a color from a `#rrggbb` hex string.
+    #[inline]
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> core::result::Result<Self, D::Error> {
+        let str = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        str.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl From<u32> for Rgb {
+    /// Converts a `0xrrggbb` value, like the ones used by Neovim, ignoring
+    /// the most significant byte.
+    #[inline]
+    fn from(color: u32) -> Self {
+        Self::new((color >> 16) as u8, (color >> 8) as u8, color as u8)
+    }
+}
+
+impl From<Rgb> for u32 {
+    #[inline]
+    fn from(color: Rgb) -> Self {
+        (color.r as u32) << 16 | (color.g as u32) << 8 | color.b as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_hex() {
+        assert_eq!("#1a2B3c".parse::<Rgb>(), Ok(Rgb::new(0x1a, 0x2b, 0x3c)));
+        assert!("1a2b3c".parse::<Rgb>().is_err());
+        assert!("#1a2b3".parse::<Rgb>().is_err());
+        assert!("#+1a2b3".parse::<Rgb>().is_err());
+    }
+
+    #[test]
+    fn cterm_round_trip() {
+        for index in 16..=255 {
+            assert_eq!(Rgb::from_cterm(index).to_cterm(), index);
+        }
+        assert_eq!(Rgb::from_cterm(208), Rgb::new(255, 135, 0));
+        assert_eq!(Rgb::new(250, 130, 10).to_cterm(), 208);
+        assert_eq!(Rgb::new(120, 121, 119).to_cterm(), 243);
+    }
+
+    #[test]
+    fn blend() {
+        let color = Rgb::new(100, 150, 200);
+        assert_eq!(color.blend(Rgb::BLACK, 0.0), color);
+        assert_eq!(color.darken(1.0), Rgb::BLACK);
+        assert_eq!(color.lighten(0.5), Rgb::new(178, 203, 228));
+    }
+
+    #[test]
+    fn contrast() {
+        let gray = Rgb::new(128, 128, 128);
+        assert_eq!(gray.contrast_ratio(gray), 1.0);
+        assert_eq!(
+            gray.most_contrasting([Rgb::WHITE, Rgb::BLACK]),
+            Some(Rgb::BLACK)
+        );
+        assert!((Rgb::WHITE.luminance() - 1.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,142 @@
+use core::num::NonZeroUsize;
+
+use serde::Deserialize;
+use types::{
+    Integer,
+    Object,
+    conversion::{self, FromObject},
+};
+
+/// A 0-indexed line number, used by most of the buffer and extmark APIs.
+///
+/// Converting to and from a [`Row1`] with [`From`] takes care of the
+/// off-by-one difference.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Deserialize,
+)]
+#[serde(transparent)]
+pub struct Row0(pub usize);
+
+/// A 1-indexed line number, used by the cursor, mark and quickfix APIs.
+///
+/// Converting to and from a [`Row0`] with [`From`] takes care of the
+/// off-by-one difference. A `Row1` is never `0`, which some APIs use to mean
+/// "no line" (e.g. unset marks): those return an `Option<Row1>` instead.
+#[derive(
+    Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Row1(NonZeroUsize);
+
+impl Row0 {
+    /// Returns the same line as a 1-indexed row.
+    #[inline]
+    pub fn to_row1(self) -> Row1 {
+        Row1(NonZeroUsize::MIN.saturating_add(self.0))
+    }
+}
+
+impl Row1 {
+    /// The first line of a buffer.
+    pub const FIRST: Self = Self(NonZeroUsize::MIN);
+
+    /// Creates a new row, returning `None` if `row` is `0`.
+    #[inline]
+    pub const fn new(row: usize) -> Option<Self> {
+        match NonZeroUsize::new(row) {
+            Some(row) => Some(Self(row)),
+            None => None,
+        }
+    }
+
+    /// Returns the row as a 1-indexed line number.
+    #[inline]
+    pub const fn get(self) -> usize {
+        self.0.get()
+    }
+
+    /// Returns the same line as a 0-indexed row.
+    #[inline]
+    pub fn to_row0(self) -> Row0 {
+        Row0(self.0.get() - 1)
+    }
+}
+
+impl From<Row1> for Row0 {
+    #[inline]
+    fn from(row: Row1) -> Self {
+        row.to_row0()
+    }
+}
+
+impl From<Row0> for Row1 {
+    #[inline]
+    fn from(row: Row0) -> Self {
+        row.to_row1()
+    }
+}
+
+impl From<Row0> for usize {
+    #[inline]
+    fn from(row: Row0) -> Self {
+        row.0
+    }
+}
+
+impl From<Row1> for usize {
+    #[inline]
+    fn from(row: Row1) -> Self {
+        row.get()
+    }
+}
+
+impl From<Row0> for Object {
+    #[inline]
+    fn from(row: Row0) -> Self {
+        (row.0 as Integer).into()
+    }
+}
+
+impl From<Row1> for Object {
+    #[inline]
+    fn from(row: Row1) -> Self {
+        (row.get() as Integer).into()
+    }
+}
+
+impl FromObject for Row0 {
+    #[inline]
+    fn from_object(obj: Object) -> Result<Self, conversion::Error> {
+        usize::from_object(obj).map(Self)
+    }
+}
+
+impl FromObject for Row1 {
+    #[inline]
+    fn from_object(obj: Object) -> Result<Self, conversion::Error> {
+        let row = usize::from_object(obj)?;
+        Ok(Self(NonZeroUsize::try_from(row)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn row_conversions() {
+        assert_eq!(Row0::from(Row1::FIRST), Row0(0));
+        assert_eq!(Row1::from(Row0(0)), Row1::FIRST);
+        assert_eq!(Row1::new(3).map(Row1::to_row0), Some(Row0(2)));
+        assert_eq!(Row1::new(0), None);
+    }
+}
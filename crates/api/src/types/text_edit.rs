@@ -0,0 +1,56 @@
+use super::{ByteCol, Row0};
+
+/// A replacement of a range of text in a buffer, like an LSP
+/// [`TextEdit`][1]. See [`Buffer::apply_edits`](crate::Buffer::apply_edits).
+///
+/// Both positions are 0-indexed, and the end position is exclusive. Lines in
+/// `new_text` are separated by `\n`.
+///
+/// [1]: https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textEdit
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct TextEdit {
+    /// The start of the replaced range.
+    pub start: (Row0, ByteCol),
+
+    /// The end of the replaced range.
+    pub end: (Row0, ByteCol),
+
+    /// The text to insert in place of the range.
+    pub new_text: String,
+}
+
+impl TextEdit {
+    /// Creates a new edit replacing the given range.
+    #[inline]
+    pub fn new(
+        start: (Row0, ByteCol),
+        end: (Row0, ByteCol),
+        new_text: impl Into<String>,
+    ) -> Self {
+        Self { start, end, new_text: new_text.into() }
+    }
+
+    /// Creates a new edit deleting the given range.
+    #[inline]
+    pub fn delete(start: (Row0, ByteCol), end: (Row0, ByteCol)) -> Self {
+        Self::new(start, end, "")
+    }
+
+    /// Creates a new edit inserting text at the given position.
+    #[inline]
+    pub fn insert(pos: (Row0, ByteCol), new_text: impl Into<String>) -> Self {
+        Self::new(pos, pos, new_text)
+    }
+
+    /// Returns the position of the end of the inserted text, once the edit
+    /// has been applied.
+    pub(crate) fn new_end(&self) -> (Row0, ByteCol) {
+        let (row, col) = self.start;
+        let mut lines = self.new_text.split('\n');
+        let first = lines.next().expect("split always yields one item");
+        match lines.enumerate().last() {
+            Some((idx, last)) => (Row0(row.0 + idx + 1), ByteCol(last.len())),
+            None => (row, ByteCol(col.0 + first.len())),
+        }
+    }
+}
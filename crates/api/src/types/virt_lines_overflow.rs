@@ -1,6 +1,13 @@
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+use serde::Deserialize;
+
+/// Controls how virtual lines wider than the window are displayed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[serde(rename_all(deserialize = "lowercase"))]
 pub enum VirtLinesOverflow {
+    /// Allow the virtual lines to scroll horizontally with the text.
     Scroll,
+
+    /// Truncate the virtual lines at the edge of the window (default).
     Trunc,
 }
 
@@ -8,7 +15,7 @@ impl VirtLinesOverflow {
     fn as_str(&self) -> &'static str {
         match self {
             Self::Scroll => "scroll",
-            Self::Trunc => "trunk",
+            Self::Trunc => "trunc",
         }
     }
 }
@@ -212,6 +212,13 @@ impl WindowConfigBuilder {
         self
     }
 
+    /// Window to split, when opening a split window.
+    #[inline]
+    pub fn win(&mut self, win: Window) -> &mut Self {
+        self.0.win = Some(win);
+        self
+    }
+
     /// Should the split window be opened as vertical.
     #[inline]
     pub fn vertical(&mut self, vertical: bool) -> &mut Self {
@@ -1,24 +1,309 @@
+use core::ffi::{CStr, c_int};
 use core::ops::{Bound, RangeBounds};
 
-use types::Integer;
+use luajit::{Poppable, Pushable, ffi::*};
+use types::{Function, Integer, LuaRef, Object, conversion::FromObject};
+
+use crate::{Error, Result};
 
 #[inline]
-pub(crate) fn range_to_limits<R>(range: R) -> (Integer, Integer)
+pub(crate) fn range_to_limits<R, T>(range: R) -> (Integer, Integer)
 where
-    R: RangeBounds<usize>,
+    R: RangeBounds<T>,
+    T: Copy + Into<usize>,
 {
     let start = match range.start_bound() {
         Bound::Unbounded => 0,
-        Bound::Excluded(&n) => (n + 1) as Integer,
-        Bound::Included(&n) => n as Integer,
+        Bound::Excluded(&n) => (n.into() + 1) as Integer,
+        Bound::Included(&n) => n.into() as Integer,
     };
 
     let end = match range.end_bound() {
         // The Neovim API generally uses -1 to indicate "until the end".
         Bound::Unbounded => -1,
-        Bound::Excluded(&n) => n as Integer,
-        Bound::Included(&n) => (n + 1) as Integer,
+        Bound::Excluded(&n) => n.into() as Integer,
+        Bound::Included(&n) => (n.into() + 1) as Integer,
     };
 
     (start, end)
 }
+
+/// Changes the signature of a [`Function`] without touching the function
+/// stored in the Lua registry.
+#[inline]
+pub(crate) fn cast_function<A, R, A2, R2>(
+    fun: Function<A, R>,
+) -> Function<A2, R2> {
+    function_from_luaref(fun.lua_ref())
+}
+
+/// Stores a new reference to the same Lua function in the registry, so that
+/// the returned [`Function`] can be handed over to Neovim (which takes
+/// ownership of its reference) while keeping the original one alive.
+#[inline]
+pub(crate) fn clone_function_ref<A, R, A2, R2>(
+    fun: &Function<A, R>,
+) -> Function<A2, R2> {
+    let lua_ref = unsafe {
+        luajit::with_state(|lstate| {
+            luajit::ffi::lua_rawgeti(
+                lstate,
+                luajit::ffi::LUA_REGISTRYINDEX,
+                fun.lua_ref(),
+            );
+            luajit::ffi::luaL_ref(lstate, luajit::ffi::LUA_REGISTRYINDEX)
+        })
+    };
+    function_from_luaref(lua_ref)
+}
+
+/// Wraps a reference to a function stored in the Lua registry in a
+/// [`Function`] with the given signature.
+#[inline]
+pub(crate) fn function_from_luaref<A, R>(lua_ref: LuaRef) -> Function<A, R> {
+    let obj = Object::from_luaref(lua_ref);
+    Function::from_object(obj).expect("object is a function")
+}
+
+/// Calls the Lua function found by indexing the `vim` global with the given
+/// path, e.g. `&[c"diagnostic", c"set"]` for `vim.diagnostic.set()`.
+///
+/// Only the first value returned by the function is popped, and the Lua
+/// stack is restored to its previous state once the call returns.
+pub(crate) fn call_vim_fn<A, R>(path: &[&CStr], args: A) -> Result<R>
+where
+    A: Pushable,
+    R: Poppable,
+{
+    call_vim_fn_n(path, args, 1)
+}
+
+/// Like [`call_vim_fn`], but adjusts the function's return values to
+/// `nresults` before popping `R`.
+pub(crate) fn call_vim_fn_n<A, R>(
+    path: &[&CStr],
+    args: A,
+    nresults: c_int,
+) -> Result<R>
+where
+    A: Pushable,
+    R: Poppable,
+{
+    call_lua(
+        |lstate| unsafe {
+            lua_getglobal(lstate, c"vim".as_ptr());
+            for field in path {
+                lua_getfield(lstate, -1, field.as_ptr());
+            }
+            0
+        },
+        args,
+        nresults,
+    )
+}
+
+/// Calls the function pushed on the stack by `push_fn`, which returns the
+/// number of arguments it pushed after the function (e.g. `1` for the
+/// `self` argument of a method call).
+///
+/// The function's return values are adjusted to `nresults` before popping
+/// `R`, and the Lua stack is restored to its previous state once the call
+/// returns.
+pub(crate) fn call_lua<P, A, R>(
+    push_fn: P,
+    args: A,
+    nresults: c_int,
+) -> Result<R>
+where
+    P: FnOnce(*mut State) -> c_int,
+    A: Pushable,
+    R: Poppable,
+{
+    unsafe {
+        luajit::with_state(move |lstate| {
+            let top = lua_gettop(lstate);
+
+            let nself = push_fn(lstate);
+
+            let res = match args.push(lstate) {
+                Ok(nargs) => {
+                    match lua_pcall(lstate, nself + nargs, nresults, 0) {
+                        LUA_OK => R::pop(lstate).map_err(Error::custom),
+                        _ => {
+                            let msg = lua_tostring(lstate, -1);
+                            Err(Error::custom(if msg.is_null() {
+                                "error calling Lua function".into()
+                            } else {
+                                CStr::from_ptr(msg).to_string_lossy()
+                            }))
+                        },
+                    }
+                },
+                Err(err) => Err(Error::custom(err)),
+            };
+
+            lua_settop(lstate, top);
+            res
+        })
+    }
+}
+
+/// An arbitrary Lua value stored in the registry, like a userdata that can't
+/// be converted to an [`Object`]. The reference is released when dropped.
+pub(crate) struct LuaValue(LuaRef);
+
+impl LuaValue {
+    /// Takes ownership of the given reference, which is released when the
+    /// value is dropped.
+    #[inline]
+    pub(crate) fn from_ref(lua_ref: LuaRef) -> Self {
+        Self(lua_ref)
+    }
+
+    /// Stores a new reference to the value referenced by `lua_ref`, leaving
+    /// the original reference untouched.
+    pub(crate) fn clone_ref(lua_ref: LuaRef) -> Self {
+        unsafe {
+            luajit::with_state(|lstate| {
+                lua_rawgeti(lstate, LUA_REGISTRYINDEX, lua_ref);
+                Self(luaL_ref(lstate, LUA_REGISTRYINDEX))
+            })
+        }
+    }
+
+    /// Calls the value as a function, adjusting its return values to
+    /// `nresults`.
+    pub(crate) fn call<A, R>(&self, args: A, nresults: c_int) -> Result<R>
+    where
+        A: Pushable,
+        R: Poppable,
+    {
+        call_lua(
+            |lstate| unsafe {
+                lua_rawgeti(lstate, LUA_REGISTRYINDEX, self.0);
+                0
+            },
+            args,
+            nresults,
+        )
+    }
+
+    /// Calls the method with the given name, i.e. `value:method(args..)`,
+    /// adjusting its return values to `nresults`.
+    pub(crate) fn call_method<A, R>(
+        &self,
+        method: &CStr,
+        args: A,
+        nresults: c_int,
+    ) -> Result<R>
+    where
+        A: Pushable,
+        R: Poppable,
+    {
+        call_lua(
+            |lstate| unsafe {
+                lua_rawgeti(lstate, LUA_REGISTRYINDEX, self.0);
+                lua_getfield(lstate, -1, method.as_ptr());
+                lua_rawgeti(lstate, LUA_REGISTRYINDEX, self.0);
+                1
+            },
+            args,
+            nresults,
+        )
+    }
+
+    /// Returns the value of the given field, i.e. `value.field`.
+    pub(crate) fn get_field<R>(&self, field: &CStr) -> Result<R>
+    where
+        R: Poppable,
+    {
+        unsafe {
+            luajit::with_state(|lstate| {
+                let top = lua_gettop(lstate);
+                lua_rawgeti(lstate, LUA_REGISTRYINDEX, self.0);
+                lua_getfield(lstate, -1, field.as_ptr());
+                let res = R::pop(lstate).map_err(Error::custom);
+                lua_settop(lstate, top);
+                res
+            })
+        }
+    }
+}
+
+impl Clone for LuaValue {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::clone_ref(self.0)
+    }
+}
+
+impl Drop for LuaValue {
+    fn drop(&mut self) {
+        luajit::function::remove(self.0);
+    }
+}
+
+impl Poppable for LuaValue {
+    unsafe fn pop(
+        lstate: *mut State,
+    ) -> core::result::Result<Self, luajit::Error> {
+        if lua_gettop(lstate) == 0 {
+            return Err(luajit::Error::PopEmptyStack);
+        }
+        Ok(Self(luaL_ref(lstate, LUA_REGISTRYINDEX)))
+    }
+}
+
+impl Pushable for LuaValue {
+    unsafe fn push(
+        self,
+        lstate: *mut State,
+    ) -> core::result::Result<c_int, luajit::Error> {
+        lua_rawgeti(lstate, LUA_REGISTRYINDEX, self.0);
+        Ok(1)
+    }
+}
+
+/// Schedules a closure to be called by the main event-loop via
+/// `vim.schedule()`, removing it from the Lua registry once it's been
+/// handed over.
+pub(crate) fn schedule<F>(fun: F) -> Result<()>
+where
+    F: FnOnce(()) + 'static,
+{
+    let fun = Function::<(), ()>::from_fn_once(fun);
+    let lua_ref = fun.lua_ref();
+    let res = call_vim_fn(&[c"schedule"], fun);
+    luajit::function::remove(lua_ref);
+    res
+}
+
+/// All the arguments a Lua function was called with, or all the values to
+/// call a Lua function with, regardless of their number.
+pub(crate) struct LuaArgs(Vec<Object>);
+
+impl Poppable for LuaArgs {
+    unsafe fn pop(
+        lstate: *mut State,
+    ) -> core::result::Result<Self, luajit::Error> {
+        let nargs = lua_gettop(lstate);
+        let mut args = (0..nargs)
+            .map(|_| Object::pop(lstate))
+            .collect::<core::result::Result<Vec<_>, _>>()?;
+        args.reverse();
+        Ok(Self(args))
+    }
+}
+
+impl Pushable for LuaArgs {
+    unsafe fn push(
+        self,
+        lstate: *mut State,
+    ) -> core::result::Result<c_int, luajit::Error> {
+        let nargs = self.0.len() as c_int;
+        for arg in self.0 {
+            arg.push(lstate)?;
+        }
+        Ok(nargs)
+    }
+}
@@ -402,13 +402,14 @@ pub fn get_keymap(mode: Mode) -> impl SuperIterator<KeymapInfos> + use<> {
 /// Binding to [`nvim_get_mark()`][1].
 ///
 /// Returns a tuple `(row, col, buffer, buffername)` representing the position
-/// of the named mark. Marks are (1,0)-indexed.
+/// of the named mark, or `None` if the mark is not set. Marks are
+/// (1,0)-indexed.
 ///
 /// [1]: https://neovim.io/doc/user/api.html#nvim_get_mark()
 pub fn get_mark(
     name: char,
     opts: &GetMarkOpts,
-) -> Result<(usize, usize, Buffer, String)> {
+) -> Result<Option<(Row1, ByteCol, Buffer, String)>> {
     let name = nvim::String::from(name);
     let mut err = nvim::Error::new();
     let mark = unsafe {
@@ -417,12 +418,12 @@ pub fn get_mark(
     choose!(err, {
         let mut iter = mark.into_iter();
         let row = usize::from_object(iter.next().expect("row is present"))?;
-        let col = usize::from_object(iter.next().expect("col is present"))?;
+        let col = ByteCol::from_object(iter.next().expect("col is present"))?;
         let buffer =
             Buffer::from_object(iter.next().expect("buffer is present"))?;
         let buffername =
             String::from_object(iter.next().expect("buffername is present"))?;
-        Ok((row, col, buffer, buffername))
+        Ok(Row1::new(row).map(|row| (row, col, buffer, buffername)))
     })
 }
 
@@ -18,7 +18,7 @@ use crate::Result;
 use crate::choose;
 use crate::ffi::window::*;
 use crate::opts::WinTextHeightOpts;
-use crate::types::WinTextHeightInfos;
+use crate::types::{ByteCol, Row1, WinTextHeightInfos};
 use crate::{Buffer, IntoResult, TabPage};
 
 /// A wrapper around a Neovim window handle.
@@ -191,14 +191,14 @@ impl Window {
     /// Gets the (1,0)-indexed cursor position in the window.
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_win_get_cursor()
-    pub fn get_cursor(&self) -> Result<(usize, usize)> {
+    pub fn get_cursor(&self) -> Result<(Row1, ByteCol)> {
         let mut err = nvim::Error::new();
         let arr =
             unsafe { nvim_win_get_cursor(self.0, types::arena(), &mut err) };
         choose!(err, {
             let mut iter = arr.into_iter();
-            let line = usize::from_object(iter.next().unwrap())?;
-            let col = usize::from_object(iter.next().unwrap())?;
+            let line = Row1::from_object(iter.next().unwrap())?;
+            let col = ByteCol::from_object(iter.next().unwrap())?;
             Ok((line, col))
         })
     }
@@ -323,9 +323,9 @@ impl Window {
     /// window even if it's not the current one.
     ///
     /// [1]: https://neovim.io/doc/user/api.html#nvim_win_set_cursor()
-    pub fn set_cursor(&mut self, line: usize, col: usize) -> Result<()> {
+    pub fn set_cursor(&mut self, line: Row1, col: ByteCol) -> Result<()> {
         let mut err = nvim::Error::new();
-        let pos = Array::from_iter([line as Integer, col as Integer]);
+        let pos = Array::from_iter([line.get() as Integer, col.0 as Integer]);
         unsafe { nvim_win_set_cursor(self.0, pos.non_owning(), &mut err) };
         choose!(err, ())
     }
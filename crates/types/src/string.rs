@@ -181,6 +181,16 @@ impl From<Cow<'_, str>> for String {
     }
 }
 
+#[cfg(unix)]
+impl From<&Path> for String {
+    #[inline]
+    fn from(path: &Path) -> Self {
+        use std::os::unix::ffi::OsStrExt;
+        Self::from_bytes(path.as_os_str().as_bytes())
+    }
+}
+
+#[cfg(not(unix))]
 impl From<&Path> for String {
     #[inline]
     fn from(path: &Path) -> Self {
@@ -188,7 +198,7 @@ impl From<&Path> for String {
     }
 }
 
-#[cfg(not(windows))]
+#[cfg(unix)]
 impl From<String> for PathBuf {
     #[inline]
     fn from(nstr: String) -> Self {
@@ -197,7 +207,7 @@ impl From<String> for PathBuf {
     }
 }
 
-#[cfg(windows)]
+#[cfg(not(unix))]
 impl From<String> for PathBuf {
     #[inline]
     fn from(nstr: String) -> Self {
@@ -1,4 +1,4 @@
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::ops;
 use std::rc::Rc;
 
@@ -18,7 +18,7 @@ fn buf_attach() {
         .build();
 
     let res = buf.attach(false, &opts);
-    assert_eq!(Ok(()), res);
+    assert!(res.is_ok(), "{res:?}");
 
     let bytes_written = api::input("ifoo<Esc>");
     assert!(bytes_written.is_ok(), "{bytes_written:?}");
@@ -41,19 +41,313 @@ fn buf_attach_on_bytes() -> Result<(), api::Error> {
             .build()
     };
 
-    buffer.attach(false, &opts)?;
+    let _attachment = buffer.attach(false, &opts)?;
 
     api::Window::current().set_buf(&buffer)?;
 
-    buffer.set_text(0..0, 0, 0, [" "])?;
-    buffer.set_text(0..0, 0, 0, [" "])?;
-    buffer.set_text(0..0, 0, 0, [" "])?;
+    buffer.set_text(Row0(0)..Row0(0), ByteCol(0), ByteCol(0), [" "])?;
+    buffer.set_text(Row0(0)..Row0(0), ByteCol(0), ByteCol(0), [" "])?;
+    buffer.set_text(Row0(0)..Row0(0), ByteCol(0), ByteCol(0), [" "])?;
 
     assert_eq!(count.get(), 3);
 
     Ok(())
 }
 
+#[nvim_oxi::test]
+fn buf_attach_on_event() -> Result<(), api::Error> {
+    let mut buffer = api::create_buf(true, false)?;
+
+    let events = Rc::new(RefCell::new(Vec::new()));
+
+    let opts = {
+        let events = events.clone();
+        BufAttachOpts::builder()
+            .on_event(move |event| {
+                events.borrow_mut().push(event);
+                false
+            })
+            .build()
+    };
+
+    let attachment = buffer.attach(false, &opts)?;
+    assert!(attachment.is_attached());
+
+    buffer.set_text(Row0(0)..Row0(0), ByteCol(0), ByteCol(0), ["foo"])?;
+
+    let events = events.borrow();
+    assert!(events.iter().any(|event| matches!(
+        event,
+        BufEvent::Lines {
+            first: Row0(0),
+            last_old: Row0(1),
+            last_new: Row0(1),
+            ..
+        }
+    )));
+    assert!(events.iter().any(|event| matches!(
+        event,
+        BufEvent::Bytes {
+            start: (Row0(0), ByteCol(0)),
+            old_end: (0, 0),
+            new_end: (0, 3),
+            new_end_byte: 3,
+            ..
+        }
+    )));
+    assert!(events.iter().all(|event| event.buffer() == &buffer));
+
+    Ok(())
+}
+
+#[nvim_oxi::test]
+fn buf_attach_detached_by_wipe() -> Result<(), api::Error> {
+    let buffer = api::create_buf(true, false)?;
+
+    let opts = BufAttachOpts::builder().on_lines(|_args| false).build();
+    let attachment = buffer.attach(false, &opts)?;
+    assert!(attachment.is_attached());
+
+    buffer.delete(&BufDeleteOpts::builder().force(true).build())?;
+    assert!(!attachment.is_attached());
+
+    Ok(())
+}
+
+#[nvim_oxi::test]
+fn buf_attach_detached_by_callback() -> Result<(), api::Error> {
+    let mut buffer = api::create_buf(true, false)?;
+
+    let opts = BufAttachOpts::builder().on_lines(|_args| true).build();
+    let attachment = buffer.attach(false, &opts)?;
+    assert!(attachment.is_attached());
+
+    buffer.set_text(Row0(0)..Row0(0), ByteCol(0), ByteCol(0), ["a"])?;
+    assert!(!attachment.is_attached());
+
+    Ok(())
+}
+
+#[nvim_oxi::test]
+fn buf_attach_detach_on_drop() -> Result<(), api::Error> {
+    let mut buffer = api::create_buf(true, false)?;
+
+    let count = Rc::new(Cell::new(0));
+
+    let opts = {
+        let count = count.clone();
+        BufAttachOpts::builder()
+            .on_lines(move |_args| {
+                count.set(count.get() + 1);
+                false
+            })
+            .build()
+    };
+
+    let attachment = buffer.attach(false, &opts)?;
+    buffer.set_text(Row0(0)..Row0(0), ByteCol(0), ByteCol(0), ["a"])?;
+    assert_eq!(count.get(), 1);
+
+    drop(attachment);
+    buffer.set_text(Row0(0)..Row0(0), ByteCol(0), ByteCol(0), ["b"])?;
+    buffer.set_text(Row0(0)..Row0(0), ByteCol(0), ByteCol(0), ["c"])?;
+    assert_eq!(count.get(), 1);
+
+    Ok(())
+}
+
+#[nvim_oxi::test]
+fn buf_mirror() -> Result<(), api::Error> {
+    let mut buffer = api::create_buf(true, false)?;
+    buffer.set_lines(.., true, ["foo", "bar", "baz"])?;
+
+    let mirror = api::BufferMirror::new(&buffer)?;
+    mirror.check_consistency()?;
+
+    buffer.set_text(Row0(0)..Row0(0), ByteCol(1), ByteCol(2), ["aa", "bb"])?;
+    mirror.check_consistency()?;
+
+    buffer.set_text(Row0(1)..Row0(3), ByteCol(1), ByteCol(2), [""])?;
+    mirror.check_consistency()?;
+
+    buffer.set_lines(Row0(0)..Row0(0), true, ["first"])?;
+    buffer.set_lines(.., true, ["only"])?;
+    mirror.check_consistency()?;
+
+    buffer.set_lines(Row0(1)..Row0(1), true, ["last"])?;
+    mirror.check_consistency()?;
+
+    assert_eq!(mirror.changedtick(), buffer.get_changedtick()?);
+
+    Ok(())
+}
+
+#[nvim_oxi::test]
+fn buf_mirror_undo_redo() -> Result<(), api::Error> {
+    let mut buffer = api::create_buf(true, false)?;
+    buffer.set_lines(.., true, ["foo bar", "baz"])?;
+
+    // Close the undo block of the initial lines, so that undoing only reverts
+    // the edits.
+    api::command("let &g:undolevels = &g:undolevels")?;
+
+    let mirror = api::BufferMirror::new(&buffer)?;
+
+    let edits = vec![
+        TextEdit::new((Row0(1), ByteCol(0)), (Row0(1), ByteCol(3)), "qux"),
+        TextEdit::insert((Row0(0), ByteCol(3)), "\nnew\nlines"),
+        TextEdit::delete((Row0(0), ByteCol(0)), (Row0(0), ByteCol(1))),
+    ];
+
+    buffer.apply_edits(edits)?;
+    mirror.check_consistency()?;
+
+    let res = buffer.call(|_| api::command("undo"));
+    assert_eq!(Ok(()), res);
+    mirror.check_consistency()?;
+    assert_eq!(mirror.snapshot().text(), b"foo bar\nbaz");
+
+    let res = buffer.call(|_| api::command("redo"));
+    assert_eq!(Ok(()), res);
+    mirror.check_consistency()?;
+    assert_eq!(mirror.snapshot().text(), b"oo\nnew\nlines bar\nqux");
+
+    assert!(!mirror.is_stale());
+    assert_eq!(mirror.changedtick(), buffer.get_changedtick()?);
+
+    Ok(())
+}
+
+#[nvim_oxi::test]
+fn buf_mirror_stale_after_wipe() -> Result<(), api::Error> {
+    let mut buffer = api::create_buf(true, false)?;
+    buffer.set_lines(.., true, ["foo", "bar"])?;
+
+    let mirror = api::BufferMirror::new(&buffer)?;
+    assert!(!mirror.is_stale());
+
+    buffer.delete(&BufDeleteOpts::builder().force(true).build())?;
+
+    assert!(mirror.is_stale());
+    assert!(mirror.error().is_none());
+    assert!(mirror.check_consistency().is_err());
+    assert_eq!(mirror.snapshot().text(), b"foo\nbar");
+
+    Ok(())
+}
+
+#[nvim_oxi::test]
+fn buf_mirror_snapshot() -> Result<(), api::Error> {
+    let mut buffer = api::create_buf(true, false)?;
+    buffer.set_lines(.., true, ["foo", "bar"])?;
+
+    let mirror = api::BufferMirror::new(&buffer)?;
+    let snapshot = mirror.snapshot();
+
+    buffer.set_lines(.., true, ["baz"])?;
+
+    let text = std::thread::spawn(move || snapshot.text()).join().unwrap();
+    assert_eq!(text, b"foo\nbar");
+    assert_eq!(mirror.snapshot().text(), b"baz");
+
+    Ok(())
+}
+
+#[nvim_oxi::test]
+fn buf_apply_edits() -> Result<(), api::Error> {
+    let mut buffer = api::create_buf(true, false)?;
+    buffer.set_lines(.., true, ["foo bar", "baz"])?;
+
+    let edits = vec![
+        TextEdit::new((Row0(1), ByteCol(0)), (Row0(1), ByteCol(3)), "qux"),
+        TextEdit::insert((Row0(0), ByteCol(3)), "\nnew"),
+        TextEdit::delete((Row0(0), ByteCol(0)), (Row0(0), ByteCol(1))),
+    ];
+
+    let inverse = buffer.apply_edits(edits)?;
+
+    let lines = buffer.get_lines(.., true)?.collect::<Vec<_>>();
+    assert_eq!(lines, ["oo", "new bar", "qux"]);
+
+    buffer.apply_edits(inverse)?;
+
+    let lines = buffer.get_lines(.., true)?.collect::<Vec<_>>();
+    assert_eq!(lines, ["foo bar", "baz"]);
+
+    Ok(())
+}
+
+#[nvim_oxi::test]
+fn buf_apply_edits_single_undo_step() -> Result<(), api::Error> {
+    let mut buffer = api::create_buf(true, false)?;
+    buffer.set_lines(.., true, ["foo bar", "baz"])?;
+
+    // Close the undo block of the initial lines, so that undoing only reverts
+    // the edits.
+    api::command("let &g:undolevels = &g:undolevels")?;
+
+    let edits = vec![
+        TextEdit::new((Row0(1), ByteCol(0)), (Row0(1), ByteCol(3)), "qux"),
+        TextEdit::insert((Row0(0), ByteCol(3)), "\nnew"),
+        TextEdit::delete((Row0(0), ByteCol(0)), (Row0(0), ByteCol(1))),
+    ];
+
+    buffer.apply_edits(edits)?;
+
+    let lines = buffer.get_lines(.., true)?.collect::<Vec<_>>();
+    assert_eq!(lines, ["oo", "new bar", "qux"]);
+
+    let res = buffer.call(|_| api::command("undo"));
+    assert_eq!(Ok(()), res);
+
+    let lines = buffer.get_lines(.., true)?.collect::<Vec<_>>();
+    assert_eq!(lines, ["foo bar", "baz"]);
+
+    Ok(())
+}
+
+#[nvim_oxi::test]
+fn buf_apply_edits_overlapping() {
+    let mut buffer = api::create_buf(true, false).unwrap();
+    buffer.set_lines(.., true, ["foo bar"]).unwrap();
+
+    let edits = vec![
+        TextEdit::new((Row0(0), ByteCol(0)), (Row0(0), ByteCol(4)), "a"),
+        TextEdit::new((Row0(0), ByteCol(2)), (Row0(0), ByteCol(6)), "b"),
+    ];
+
+    assert!(buffer.apply_edits(edits).is_err());
+
+    let lines = buffer.get_lines(.., true).unwrap().collect::<Vec<_>>();
+    assert_eq!(lines, ["foo bar"]);
+}
+
+#[nvim_oxi::test]
+fn buf_replace_contents_minimal() -> Result<(), api::Error> {
+    let mut buffer = api::create_buf(true, false)?;
+    buffer.set_lines(.., true, ["fn main() {", "let x=1;", "}", "// end"])?;
+
+    let ns_id = api::create_namespace("buf_replace_contents_minimal");
+    let opts = SetExtmarkOpts::default();
+    let extmark_id = buffer.set_extmark(ns_id, Row0(3), ByteCol(3), &opts)?;
+
+    buffer.replace_contents_minimal(
+        "// start\nfn main() {\n    let x = 1;\n}\n// end\n",
+    )?;
+
+    let lines = buffer.get_lines(.., true)?.collect::<Vec<_>>();
+    assert_eq!(
+        lines,
+        ["// start", "fn main() {", "    let x = 1;", "}", "// end"]
+    );
+
+    let (row, col, _) =
+        buffer.get_extmark_by_id(ns_id, extmark_id, &Default::default())?;
+    assert_eq!((row, col), (Row0(4), ByteCol(3)));
+
+    Ok(())
+}
+
 #[nvim_oxi::test]
 fn buf_call_nil() {
     let buf = Buffer::current();
@@ -68,6 +362,29 @@ fn buf_call_int() {
     assert_eq!(Ok(42), res);
 }
 
+#[nvim_oxi::test]
+fn buf_convert_position() -> Result<(), api::Error> {
+    let mut buffer = api::create_buf(true, false)?;
+    buffer.set_lines(.., true, ["foo", "aé😀b"])?;
+
+    let pos = buffer.convert_position(
+        Position::utf16(Row0(1), 4),
+        PositionEncoding::Utf8,
+    )?;
+    assert_eq!(pos, Position::byte(Row0(1), 7));
+
+    let pos = buffer.convert_position(pos, PositionEncoding::Utf32)?;
+    assert_eq!(pos, Position::utf32(Row0(1), 3));
+
+    let res = buffer.convert_position(
+        Position::utf16(Row0(1), 10),
+        PositionEncoding::Utf8,
+    );
+    assert!(res.is_err());
+
+    Ok(())
+}
+
 #[nvim_oxi::test]
 fn buf_create_del_user_command() {
     let mut buf = Buffer::current();
@@ -106,24 +423,25 @@ fn buf_get_lines_range_bounds() {
     let lines = buf.get_lines(.., true).unwrap().collect::<Vec<_>>();
     assert_eq!(lines, [""]);
 
-    buf.set_text(0..0, 0, 0, ["Hello", "world"]).unwrap();
+    buf.set_text(Row0(0)..Row0(0), ByteCol(0), ByteCol(0), ["Hello", "world"])
+        .unwrap();
 
-    for bound in [Range::new(..1), Range::new(0..1)] {
+    for bound in [Range::new(..Row0(1)), Range::new(Row0(0)..Row0(1))] {
         let lines = buf.get_lines(bound, true).unwrap().collect::<Vec<_>>();
         assert_eq!(lines, ["Hello"]);
     }
 
-    for bound in [Range::new(1..), Range::new(1..2)] {
+    for bound in [Range::new(Row0(1)..), Range::new(Row0(1)..Row0(2))] {
         let lines = buf.get_lines(bound, true).unwrap().collect::<Vec<_>>();
         assert_eq!(lines, ["world"]);
     }
 
     for bound in [
         Range::new(..),
-        Range::new(..2),
-        Range::new(0..2),
-        Range::new(..=1),
-        Range::new(0..=1),
+        Range::new(..Row0(2)),
+        Range::new(Row0(0)..Row0(2)),
+        Range::new(..=Row0(1)),
+        Range::new(Row0(0)..=Row0(1)),
     ] {
         let lines = buf.get_lines(bound, true).unwrap().collect::<Vec<_>>();
         assert_eq!(lines, ["Hello", "world"]);
@@ -137,6 +455,69 @@ fn buf_get_name_invalid_buf() {
     assert!(matches!(err, api::Error::Nvim(_)));
 }
 
+#[nvim_oxi::test]
+fn buf_lines_chunked() -> Result<(), api::Error> {
+    let mut buffer = api::create_buf(true, false)?;
+    let lines = (0..10).map(|n| n.to_string()).collect::<Vec<_>>();
+    buffer.set_lines(.., true, lines.iter().map(String::as_str))?;
+
+    let chunks = buffer.lines_chunked(Row0(1).., 4)?;
+    assert_eq!(chunks.len(), 3);
+
+    let chunks = chunks.collect::<Result<Vec<_>, _>>()?;
+    let sizes = chunks.iter().map(Vec::len).collect::<Vec<_>>();
+    assert_eq!(sizes, [4, 4, 1]);
+    assert_eq!(chunks.concat(), lines[1..]);
+
+    assert!(buffer.lines_chunked(..Row0(11), 4).is_err());
+
+    Ok(())
+}
+
+#[nvim_oxi::test]
+fn buf_for_each_line() -> Result<(), api::Error> {
+    let mut buffer = api::create_buf(true, false)?;
+    buffer.set_lines(.., true, ["foo", "bar", "baz", "bar"])?;
+
+    let mut visited = 0;
+    let found = buffer.for_each_line(Row0(1).., |row, line| {
+        visited += 1;
+        if line.as_bytes() == b"bar" {
+            ops::ControlFlow::Break(row)
+        } else {
+            ops::ControlFlow::Continue(())
+        }
+    })?;
+
+    assert_eq!(found, Some(Row0(1)));
+    assert_eq!(visited, 1);
+
+    let mut total_len = 0;
+    let found = buffer.for_each_line(.., |_, line| {
+        total_len += line.len();
+        ops::ControlFlow::<()>::Continue(())
+    })?;
+
+    assert_eq!(found, None);
+    assert_eq!(total_len, 12);
+
+    // Rows keep counting across the chunks the lines are read in.
+    let lines = (0..3000).map(|n| n.to_string()).collect::<Vec<_>>();
+    buffer.set_lines(.., true, lines.iter().map(String::as_str))?;
+
+    let found = buffer.for_each_line(.., |row, line| {
+        if line.as_bytes() != row.0.to_string().as_bytes() {
+            ops::ControlFlow::Break(row)
+        } else {
+            ops::ControlFlow::Continue(())
+        }
+    })?;
+
+    assert_eq!(found, None);
+
+    Ok(())
+}
+
 #[nvim_oxi::test]
 fn buf_loaded_n_valid() {
     let buf = Buffer::current();
@@ -233,15 +614,36 @@ fn buf_set_get_del_lines() {
     assert_eq!(Ok(1), buf.line_count());
 }
 
+#[nvim_oxi::test]
+fn buf_set_get_lines_bytes() -> Result<(), api::Error> {
+    let mut buffer = api::create_buf(true, false)?;
+    let lines: [&[u8]; 2] = [b"caf\xe9", b"\xff\xfe"];
+    buffer.set_lines_bytes(.., true, lines)?;
+
+    let got = buffer.get_lines_bytes(.., true)?.collect::<Vec<_>>();
+    assert_eq!(got, lines);
+
+    let got = buffer
+        .get_text_bytes(.., ByteCol(1), ByteCol(1), &Default::default())?
+        .collect::<Vec<_>>();
+    assert_eq!(got, [&b"af\xe9"[..], b"\xff"]);
+
+    let edit = TextEdit::delete((Row0(1), ByteCol(0)), (Row0(1), ByteCol(1)));
+    let err = buffer.apply_edits(vec![edit]).unwrap_err();
+    assert!(matches!(err, api::Error::LineFromUtf8 { line: 1, .. }));
+
+    Ok(())
+}
+
 #[nvim_oxi::test]
 fn buf_set_get_del_mark() {
     let mut buf = Buffer::current();
     let opts = SetMarkOpts::default();
 
-    let res = buf.set_mark('a', 1, 0, &opts);
+    let res = buf.set_mark('a', Row1::FIRST, ByteCol(0), &opts);
     assert_eq!(Ok(()), res);
 
-    assert_eq!((1, 0), buf.get_mark('a').unwrap());
+    assert_eq!(Some((Row1::FIRST, ByteCol(0))), buf.get_mark('a').unwrap());
 
     let res = buf.del_mark('a');
     assert_eq!(Ok(()), res);
@@ -251,10 +653,13 @@ fn buf_set_get_del_mark() {
 fn buf_set_get_del_text() {
     let mut buf = Buffer::current();
 
-    assert_eq!(Ok(()), buf.set_text(.., 0, 0, ["foo", "bar", "baz"]));
+    assert_eq!(
+        Ok(()),
+        buf.set_text(.., ByteCol(0), ByteCol(0), ["foo", "bar", "baz"])
+    );
     assert_eq!(
         vec!["foo", "bar", "baz"],
-        buf.get_text(.., 0, 3, &Default::default())
+        buf.get_text(.., ByteCol(0), ByteCol(3), &Default::default())
             .unwrap()
             .map(|s| s.to_string_lossy().into())
             .collect::<Vec<String>>()
@@ -263,17 +668,22 @@ fn buf_set_get_del_text() {
 
     assert_eq!(
         vec!["oo", "ba"],
-        buf.get_text(..1, 1, 2, &Default::default())
+        buf.get_text(..Row0(1), ByteCol(1), ByteCol(2), &Default::default())
             .unwrap()
             .map(|s| s.to_string_lossy().into())
             .collect::<Vec<String>>()
     );
 
-    assert_eq!(Ok(()), buf.set_text::<&str, _, _>(.., 0, 3, []));
+    assert_eq!(
+        Ok(()),
+        buf.set_text::<&str, _, _>(.., ByteCol(0), ByteCol(3), [])
+    );
 
     assert_eq!(
         1,
-        buf.get_text(.., 0, 1, &Default::default()).unwrap().count()
+        buf.get_text(.., ByteCol(0), ByteCol(1), &Default::default())
+            .unwrap()
+            .count()
     );
 
     assert_eq!(Ok(1), buf.line_count());
@@ -1,4 +1,5 @@
-use nvim_oxi::api::{self, opts::*, types::*};
+use nvim_oxi::Function;
+use nvim_oxi::api::{self, Buffer, opts::*, types::*};
 
 #[nvim_oxi::test]
 fn command_nargs_0() {
@@ -27,3 +28,58 @@ fn regression_1() {
         .build();
     api::create_user_command("Echo", "", &opts).unwrap();
 }
+
+#[nvim_oxi::test]
+fn command_preview() {
+    let ns_id = api::create_namespace("command-preview");
+
+    let preview = |(args, ns_id, buf): (CommandArgs, u32, Option<Buffer>)| {
+        assert_eq!(None, buf);
+        let text = args.args.unwrap_or_default();
+        let mut buf = Buffer::current();
+        buf.set_lines(.., false, [text])?;
+        let opts = SetExtmarkOpts::builder()
+            .end_col(ByteCol(1))
+            .hl_group("Search")
+            .build();
+        buf.set_extmark(ns_id, Row0(0), ByteCol(0), &opts)?;
+        Ok::<_, api::Error>(PreviewMode::Preview)
+    };
+
+    let opts = CreateCommandOpts::builder()
+        .nargs(CommandNArgs::One)
+        .preview(preview)
+        .build();
+    api::create_user_command("Foo", ":", &opts).unwrap();
+    assert_eq!(api::command("Foo foo"), Ok(()));
+
+    // 'inccommand' only calls the preview while the command is being typed,
+    // so call it directly with the arguments Neovim gives to commands.
+    let preview = Function::<_, PreviewMode>::from_fn(preview);
+    api::command(&format!(
+        "lua vim.api.nvim_create_user_command('CallPreview', function(args) \
+         vim.g.preview_mode = debug.getregistry()[{}](args, {ns_id}, nil) \
+         end, {{ nargs = 1 }})",
+        preview.lua_ref(),
+    ))
+    .unwrap();
+
+    api::command("CallPreview bar").unwrap();
+
+    assert_eq!(Ok(1), api::get_var::<i64>("preview_mode"));
+
+    let buf = Buffer::current();
+    let lines = buf.get_lines(.., true).unwrap();
+    assert_eq!(vec!["bar"], lines.map(|l| l.to_string()).collect::<Vec<_>>());
+
+    let opts = GetExtmarksOpts::builder().build();
+    let extmarks = buf
+        .get_extmarks(
+            ns_id,
+            ExtmarkPosition::ByTuple((Row0(0), ByteCol(0))),
+            ExtmarkPosition::ByTuple((Row0(0), ByteCol(0))),
+            &opts,
+        )
+        .unwrap();
+    assert_eq!(1, extmarks.count());
+}
@@ -0,0 +1,42 @@
+use nvim_oxi::api::{
+    self,
+    Buffer,
+    diagnostic::*,
+    types::{ByteCol, Row0},
+};
+
+#[nvim_oxi::test]
+fn diagnostic_set_get_reset() {
+    let ns = api::create_namespace("Foo");
+    let buf = Buffer::current();
+
+    let diagnostics = vec![
+        Diagnostic::new(Row0(0), ByteCol(0), Severity::Warn, "foo")
+            .end(Row0(0), ByteCol(3))
+            .source("oxi")
+            .code("W001"),
+        Diagnostic::new(Row0(1), ByteCol(2), Severity::Error, "bar"),
+    ];
+
+    set(ns, &buf, diagnostics).unwrap();
+
+    let got = get(Some(ns), Some(&buf)).unwrap();
+    assert_eq!(2, got.len());
+    assert_eq!(Severity::Warn, got[0].severity);
+    assert_eq!("foo", got[0].message);
+    assert_eq!(Some(ByteCol(3)), got[0].end_col);
+    assert_eq!(Some("oxi".to_owned()), got[0].source);
+    assert_eq!(Some("W001".to_owned()), got[0].code);
+    assert_eq!(Some(ns), got[0].namespace);
+    assert_eq!(Some(buf.clone()), got[1].bufnr);
+
+    reset(Some(ns), Some(&buf)).unwrap();
+    assert!(get(Some(ns), Some(&buf)).unwrap().is_empty());
+}
+
+#[nvim_oxi::test]
+fn diagnostic_config() {
+    let ns = api::create_namespace("Foo");
+    let config = DiagnosticConfig::default().virtual_text(false).signs(true);
+    assert_eq!(Ok(()), api::diagnostic::config(Some(ns), &config));
+}
@@ -7,7 +7,7 @@ use nvim_oxi::api::{self, Buffer, opts::*, types::*};
 fn add_highlight() {
     let mut buf = Buffer::current();
     let id = api::create_namespace("Foo");
-    let res = buf.add_highlight(id, "Normal", 0, ..);
+    let res = buf.add_highlight(id, "Normal", Row0(0), ..);
     assert!(res.is_ok(), "{res:?}");
 }
 
@@ -28,7 +28,7 @@ fn get_extmarks() {
         .conceal(Some('a'))
         .cursorline_hl_group("Foo")
         .end_right_gravity(true)
-        .end_row(0)
+        .end_row(Row0(0))
         .hl_group("Bar")
         .hl_mode(ExtmarkHlMode::Combine)
         .virt_lines([[("foo", "Foo"), ("bar", "Bar")]])
@@ -36,7 +36,8 @@ fn get_extmarks() {
         .virt_text_pos(ExtmarkVirtTextPosition::Overlay)
         .build();
 
-    let extmark_id = buf.set_extmark(ns_id, 0, 0, &opts).unwrap();
+    let extmark_id =
+        buf.set_extmark(ns_id, Row0(0), ByteCol(0), &opts).unwrap();
 
     let start = ExtmarkPosition::ById(extmark_id);
     let end = ExtmarkPosition::ById(extmark_id);
@@ -58,9 +59,9 @@ fn get_extmarks() {
     let infos = infos.unwrap();
 
     assert_eq!(extmark_id, id);
-    assert_eq!((row, col), (0, 0));
+    assert_eq!((row, col), (Row0(0), ByteCol(0)));
     assert_eq!(Some(true), infos.end_right_gravity);
-    assert_eq!(Some(0), infos.end_row);
+    assert_eq!(Some(Row0(0)), infos.end_row);
     assert_eq!(infos.hl_group.unwrap(), String::from("Bar"));
     assert_eq!(Some(ExtmarkHlMode::Combine), infos.hl_mode);
 
@@ -133,7 +134,7 @@ fn set_decoration_provider() {
     let res = api::set_decoration_provider(id, &opts);
     assert_eq!(Ok(()), res);
 
-    api::Buffer::current().set_lines(0..0, true, ["foo"]).unwrap();
+    api::Buffer::current().set_lines(Row0(0)..Row0(0), true, ["foo"]).unwrap();
     api::command("redraw!").expect("redraw failed");
 
     assert!(on_start_called.get());
@@ -160,7 +161,8 @@ fn set_extmark_via_group_id() {
         .virt_text_pos(ExtmarkVirtTextPosition::Overlay)
         .build();
 
-    let extmark_id = buf.set_extmark(ns_id, 0, 0, &opts).unwrap();
+    let extmark_id =
+        buf.set_extmark(ns_id, Row0(0), ByteCol(0), &opts).unwrap();
 
     let opts = GetExtmarkByIdOpts::builder().details(true).build();
 
@@ -196,7 +198,7 @@ fn set_get_del_extmark() {
         .conceal(Some('a'))
         .cursorline_hl_group("Foo")
         .end_right_gravity(true)
-        .end_row(0)
+        .end_row(Row0(0))
         .hl_group("Bar")
         .hl_mode(ExtmarkHlMode::Combine)
         .virt_lines([[("foo", "Foo"), ("bar", "Bar")]])
@@ -204,7 +206,7 @@ fn set_get_del_extmark() {
         .virt_text_pos(ExtmarkVirtTextPosition::Overlay)
         .build();
 
-    let res = buf.set_extmark(ns_id, 0, 0, &opts);
+    let res = buf.set_extmark(ns_id, Row0(0), ByteCol(0), &opts);
     assert!(res.is_ok(), "{res:?}");
 
     let extmark_id = res.unwrap();
@@ -214,13 +216,13 @@ fn set_get_del_extmark() {
     assert!(got.is_ok(), "{got:?}");
 
     let (row, col, infos) = got.unwrap();
-    assert_eq!((row, col), (0, 0));
+    assert_eq!((row, col), (Row0(0), ByteCol(0)));
 
     assert!(infos.is_some(), "no informations were returned");
 
     let infos = infos.unwrap();
     assert_eq!(Some(true), infos.end_right_gravity);
-    assert_eq!(Some(0), infos.end_row);
+    assert_eq!(Some(Row0(0)), infos.end_row);
     assert_eq!(infos.hl_group.unwrap(), String::from("Bar"));
     assert_eq!(Some(ExtmarkHlMode::Combine), infos.hl_mode);
 
@@ -243,6 +245,160 @@ fn set_get_del_extmark() {
     assert_eq!(Ok(()), res);
 }
 
+#[nvim_oxi::test]
+fn register_decoration_provider() {
+    struct Counter {
+        lines: Rc<Cell<usize>>,
+        ends: Rc<Cell<usize>>,
+    }
+
+    impl api::DecorationProvider for Counter {
+        fn on_line(&mut self, _: api::Window, _: Buffer, _: Row0) {
+            self.lines.set(self.lines.get() + 1);
+        }
+
+        fn on_end(&mut self, _: u32) {
+            self.ends.set(self.ends.get() + 1);
+        }
+    }
+
+    let lines = Rc::new(Cell::new(0));
+    let ends = Rc::new(Cell::new(0));
+
+    let ns_id = api::create_namespace("decoration_provider_handle");
+    let counter = Counter { lines: lines.clone(), ends: ends.clone() };
+    let handle = api::register_decoration_provider(ns_id, counter).unwrap();
+    assert_eq!(handle.namespace(), ns_id);
+
+    Buffer::current().set_lines(.., true, ["foo", "bar"]).unwrap();
+    api::command("redraw!").unwrap();
+
+    assert!(lines.get() >= 2);
+    assert!(ends.get() >= 1);
+
+    handle.disable();
+    lines.set(0);
+    ends.set(0);
+
+    api::command("redraw!").unwrap();
+
+    assert_eq!(lines.get(), 0);
+    assert_eq!(ends.get(), 0);
+}
+
+#[nvim_oxi::test]
+fn register_decoration_provider_replaced() {
+    struct Counter(Rc<Cell<usize>>);
+
+    impl api::DecorationProvider for Counter {
+        fn on_end(&mut self, _: u32) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let first = Rc::new(Cell::new(0));
+    let second = Rc::new(Cell::new(0));
+
+    let ns_id = api::create_namespace("decoration_provider_replaced");
+    let first_handle =
+        api::register_decoration_provider(ns_id, Counter(first.clone()))
+            .unwrap();
+    let second_handle =
+        api::register_decoration_provider(ns_id, Counter(second.clone()))
+            .unwrap();
+
+    // Dropping the handle of the replaced provider doesn't disable the new
+    // one.
+    drop(first_handle);
+
+    api::command("redraw!").unwrap();
+
+    assert_eq!(first.get(), 0);
+    assert!(second.get() >= 1);
+
+    drop(second_handle);
+    second.set(0);
+
+    api::command("redraw!").unwrap();
+
+    assert_eq!(second.get(), 0);
+}
+
+#[nvim_oxi::test]
+fn set_extmark_opts_from_infos() {
+    let mut buf = api::create_buf(true, false).unwrap();
+    buf.set_lines(.., true, ["foo", "bar"]).unwrap();
+    let ns_id = api::create_namespace("Foo");
+
+    let opts = SetExtmarkOpts::builder()
+        .end_row(Row0(1))
+        .end_col(ByteCol(2))
+        .hl_group("Comment")
+        .line_hl_group("CursorLine")
+        .number_hl_group("LineNr")
+        .sign_text("S")
+        .url("https://neovim.io")
+        .virt_lines([[("foo", "Comment")]])
+        .virt_lines_overflow(VirtLinesOverflow::Scroll)
+        .virt_text([("bar", vec!["Comment", "Error"])])
+        .virt_text_win_col(4)
+        .build();
+
+    let id = buf.set_extmark(ns_id, Row0(0), ByteCol(1), &opts).unwrap();
+
+    let opts = GetExtmarkByIdOpts::builder().details(true).build();
+    let (row, col, infos) = buf.get_extmark_by_id(ns_id, id, &opts).unwrap();
+    let infos = infos.unwrap();
+
+    assert_eq!(infos.ns_id, Some(ns_id));
+    assert_eq!(infos.line_hl_group, Some("CursorLine".into()));
+    assert_eq!(infos.number_hl_group, Some("LineNr".into()));
+    assert_eq!(infos.sign_text.as_deref(), Some("S "));
+    assert_eq!(infos.url.as_deref(), Some("https://neovim.io"));
+    assert_eq!(infos.virt_lines_overflow, Some(VirtLinesOverflow::Scroll));
+    assert_eq!(infos.virt_text_pos, None);
+
+    buf.del_extmark(ns_id, id).unwrap();
+
+    let opts = SetExtmarkOpts::from(&infos);
+    let id = buf.set_extmark(ns_id, row, col, &opts).unwrap();
+
+    let opts = GetExtmarkByIdOpts::builder().details(true).build();
+    let restored = buf.get_extmark_by_id(ns_id, id, &opts).unwrap();
+    assert_eq!(restored, (row, col, Some(infos)));
+}
+
+#[nvim_oxi::test]
+fn virt_text_pos_win_col() {
+    let mut buf = Buffer::current();
+
+    let ns_id = api::create_namespace("test");
+
+    let opts = SetExtmarkOpts::builder()
+        .virt_text([("foo", "Comment")])
+        .virt_text_pos(ExtmarkVirtTextPosition::Overlay)
+        .virt_text_win_col(2)
+        .build();
+
+    let extmark_id =
+        buf.set_extmark(ns_id, Row0(0), ByteCol(0), &opts).unwrap();
+
+    let opts = GetExtmarkByIdOpts::builder().details(true).build();
+
+    let Ok((_, _, Some(infos))) =
+        buf.get_extmark_by_id(ns_id, extmark_id, &opts)
+    else {
+        panic!("couldn't get extmark infos");
+    };
+
+    assert_eq!(None, infos.virt_text_pos);
+    assert_eq!(Some(2), infos.virt_text_win_col);
+
+    let opts = SetExtmarkOpts::from(&infos);
+    let res = buf.set_extmark(ns_id, Row0(0), ByteCol(0), &opts);
+    assert!(res.is_ok(), "{res:?}");
+}
+
 #[nvim_oxi::test]
 fn virt_text_pos_inline() {
     let mut buf = Buffer::current();
@@ -254,7 +410,8 @@ fn virt_text_pos_inline() {
         .virt_text_pos(ExtmarkVirtTextPosition::Inline)
         .build();
 
-    let extmark_id = buf.set_extmark(ns_id, 0, 0, &opts).unwrap();
+    let extmark_id =
+        buf.set_extmark(ns_id, Row0(0), ByteCol(0), &opts).unwrap();
 
     let opts = GetExtmarkByIdOpts::builder().details(true).build();
 
@@ -266,3 +423,123 @@ fn virt_text_pos_inline() {
 
     assert_eq!(infos.virt_text_pos, Some(ExtmarkVirtTextPosition::Inline));
 }
+
+#[nvim_oxi::test]
+fn anchor_follows_edits() -> Result<(), api::Error> {
+    let mut buf = api::create_buf(true, false)?;
+    buf.set_lines(.., true, ["foo bar"])?;
+
+    let left = api::Anchor::new(&buf, (Row0(0), ByteCol(4)), Gravity::Left)?;
+    let right = api::Anchor::new(&buf, (Row0(0), ByteCol(4)), Gravity::Right)?;
+
+    buf.set_lines(Row0(0)..Row0(0), true, ["first"])?;
+    buf.set_text(Row0(1)..Row0(1), ByteCol(4), ByteCol(4), ["baz "])?;
+
+    assert_eq!(left.position()?, (Row0(1), ByteCol(4)));
+    assert_eq!(right.position()?, (Row0(1), ByteCol(8)));
+
+    let ns_id = api::create_namespace("nvim-oxi-anchors");
+    let count = || {
+        buf.get_extmarks(
+            ns_id,
+            ExtmarkPosition::ByTuple((Row0(0), ByteCol(0))),
+            ExtmarkPosition::ByTuple((Row0(buf.line_count()?), ByteCol(0))),
+            &Default::default(),
+        )
+        .map(|extmarks| extmarks.count())
+    };
+
+    assert_eq!(count()?, 2);
+    drop(left);
+    assert_eq!(count()?, 1);
+
+    Ok(())
+}
+
+#[nvim_oxi::test]
+fn highlight_layer() -> Result<(), api::Error> {
+    let mut buf = api::create_buf(true, false)?;
+    buf.set_lines(.., true, ["fn main() {", "    foo();", "}"])?;
+
+    let ns_id = api::create_namespace("highlight_layer");
+    let mut layer = api::HighlightLayer::new(&buf, ns_id);
+
+    let span = |row, start, end, hl_group| {
+        HighlightSpan::new(
+            (Row0(row), ByteCol(start)),
+            (Row0(row), ByteCol(end)),
+            hl_group,
+        )
+    };
+
+    let extmarks = |buf: &Buffer| {
+        let opts = GetExtmarksOpts::builder().details(true).build();
+        buf.get_extmarks(
+            ns_id,
+            ExtmarkPosition::ByTuple((Row0(0), ByteCol(0))),
+            ExtmarkPosition::ByTuple((Row0(buf.line_count()?), ByteCol(0))),
+            &opts,
+        )
+        .map(|extmarks| {
+            extmarks
+                .map(|(id, row, col, infos)| {
+                    let hl_group = infos.unwrap().hl_group.unwrap();
+                    (id, row, col, hl_group)
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    layer.update([span(0, 0, 2, "Keyword"), span(1, 4, 7, "Function")])?;
+    let before = extmarks(&buf)?;
+    assert_eq!(before.len(), 2);
+
+    // The unchanged span keeps its extmark.
+    layer.update([span(0, 0, 2, "Keyword"), span(1, 4, 7, "Identifier")])?;
+    let after = extmarks(&buf)?;
+    assert_eq!(after.len(), 2);
+    assert_eq!(after[0], before[0]);
+    assert_eq!(after[1].3, String::from("Identifier"));
+
+    // Spans outside of the updated rows are left alone.
+    layer.update_rows(Row0(1)..Row0(2), [span(0, 3, 7, "Function")])?;
+    assert_eq!(extmarks(&buf)?, [after[0].clone()]);
+
+    // Spans past the end of the line are clamped instead of failing.
+    layer.update([span(0, 0, 2, "Keyword"), span(2, 0, 50, "Comment")])?;
+    let clamped = extmarks(&buf)?;
+    assert_eq!(clamped.len(), 2);
+    assert_eq!(clamped[0], after[0]);
+
+    // The clamped span matches its extmark, so it isn't added again.
+    layer.update([span(0, 0, 2, "Keyword"), span(2, 0, 50, "Comment")])?;
+    assert_eq!(extmarks(&buf)?, clamped);
+
+    drop(layer);
+    assert!(extmarks(&buf)?.is_empty());
+
+    Ok(())
+}
+
+#[nvim_oxi::test]
+fn range_anchor_follows_edits() -> Result<(), api::Error> {
+    let mut buf = api::create_buf(true, false)?;
+    buf.set_lines(.., true, ["foo bar baz"])?;
+
+    let anchor = api::RangeAnchor::new(
+        &buf,
+        (Row0(0), ByteCol(4)),
+        (Row0(0), ByteCol(7)),
+        (Gravity::Left, Gravity::Right),
+    )?;
+
+    buf.set_text(Row0(0)..Row0(0), ByteCol(7), ByteCol(7), ["!"])?;
+    buf.set_text(Row0(0)..Row0(0), ByteCol(0), ByteCol(4), [""])?;
+
+    assert_eq!(
+        anchor.range()?,
+        ((Row0(0), ByteCol(0)), (Row0(0), ByteCol(4)))
+    );
+
+    Ok(())
+}
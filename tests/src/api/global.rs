@@ -1,3 +1,6 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
 use all_asserts::*;
 use nvim_oxi::api::{self, Buffer, Window, opts::*, types::*};
 
@@ -81,7 +84,7 @@ fn feedkeys() {
     api::feedkeys(&keys, c"x", false);
 
     let lines = api::Buffer::current()
-        .get_lines(0..1, true)
+        .get_lines(Row0(0)..Row0(1), true)
         .unwrap()
         .collect::<Vec<_>>();
 
@@ -185,6 +188,73 @@ fn hl_underline() {
     assert_eq!(Some(true), infos.underline);
 }
 
+#[nvim_oxi::test]
+fn highlight_round_trip() {
+    let hl = Highlight {
+        fg: Some(Rgb::parse("#ff8700").unwrap()),
+        bg: Some(Rgb::parse("DarkBlue").unwrap()),
+        ctermfg: Some(Rgb::parse("208").unwrap().to_cterm()),
+        bold: true,
+        underdotted: true,
+        ..Default::default()
+    };
+
+    api::set_hl(0, "OxiHighlight", &SetHighlightOpts::from(&hl)).unwrap();
+
+    let GetHlInfos::Single(infos) = api::get_hl(
+        0,
+        &GetHighlightOpts::builder().name("OxiHighlight").build(),
+    )
+    .unwrap() else {
+        panic!("expected a single");
+    };
+
+    assert_eq!(Highlight::from(infos), hl);
+    assert_eq!(hl.bg, Some(Rgb::new(0, 0, 0x8b)));
+}
+
+#[nvim_oxi::test]
+fn resolve_hl() {
+    let base = Highlight {
+        fg: Some(Rgb::new(0x12, 0x34, 0x56)),
+        italic: true,
+        ..Default::default()
+    };
+
+    api::set_hl(0, "OxiBase", &SetHighlightOpts::from(&base)).unwrap();
+
+    let link = SetHighlightOpts::from(&Highlight::linked_to("OxiBase"));
+    api::set_hl(0, "OxiMiddle", &link).unwrap();
+
+    let link = SetHighlightOpts::from(&Highlight::linked_to("OxiMiddle"));
+    api::set_hl(0, "OxiTop", &link).unwrap();
+
+    assert_eq!(api::resolve_hl("OxiTop").unwrap(), base);
+    assert!(api::resolve_hl("OxiDoesNotExist").is_err());
+}
+
+#[nvim_oxi::test]
+fn export_colorscheme() {
+    let ns_id = api::create_namespace("oxi_colorscheme");
+
+    let title =
+        Highlight { bg: Some(Rgb::BLACK), bold: true, ..Default::default() };
+
+    api::set_hl(ns_id, "Title", &SetHighlightOpts::from(&title)).unwrap();
+
+    let link = SetHighlightOpts::from(&Highlight::linked_to("Title"));
+    api::set_hl(ns_id, "Header", &link).unwrap();
+
+    let colorscheme = api::export_colorscheme(ns_id).unwrap();
+    assert_eq!(colorscheme.groups.len(), 2);
+    assert_eq!(colorscheme.groups["Title"], title);
+    assert_eq!(colorscheme.groups["Header"], Highlight::linked_to("Title"));
+
+    let other_ns_id = api::create_namespace("oxi_colorscheme_copy");
+    colorscheme.apply(other_ns_id).unwrap();
+    assert_eq!(api::export_colorscheme(other_ns_id).unwrap(), colorscheme);
+}
+
 #[nvim_oxi::test]
 fn list_bufs() {
     let _ = api::create_buf(true, false);
@@ -246,17 +316,149 @@ fn set_get_del_keymap() {
     assert_eq!(Ok(()), res);
 }
 
+#[nvim_oxi::test]
+fn set_get_del_expr_keymap() {
+    let buf = Buffer::current();
+
+    let keymap = api::Keymap::new(Mode::Normal, "<Space>x")
+        .expr(|()| String::from("<Nop>"))
+        .desc("does nothing")
+        .buffer(&buf);
+
+    assert_eq!(Ok(()), keymap.set());
+
+    let keymap_infos = buf
+        .get_keymap(Mode::Normal)
+        .unwrap()
+        .find(|keymap| keymap.desc.as_deref() == Some("does nothing"))
+        .unwrap();
+
+    assert!(keymap_infos.expr);
+
+    let Some(KeymapCallback::Expr(callback)) = keymap_infos.callback else {
+        panic!("expected an expr callback");
+    };
+
+    assert_eq!(Ok(String::from("<Nop>")), callback.call(()));
+
+    assert_eq!(Ok(()), keymap.del());
+}
+
+#[nvim_oxi::test]
+fn key_sequence_to_termcodes() {
+    let keys = KeySequence::from_iter([Key::ctrl('w'), Key::from('l')]);
+    assert_eq!("<C-w>l", keys.to_string());
+    assert_eq!(
+        api::replace_termcodes("<C-w>l", true, false, true),
+        keys.to_termcodes()
+    );
+    assert_eq!(keys, KeySequence::parse("<c-w>l"));
+}
+
+#[nvim_oxi::test]
+fn operator_with_dot_repeat() {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, ["foo bar"]).unwrap();
+    Window::current().set_cursor(Row1::FIRST, ByteCol(0)).unwrap();
+
+    let ranges = Rc::new(RefCell::new(Vec::new()));
+
+    let keys = api::operator({
+        let ranges = Rc::clone(&ranges);
+        move |motion_type, range| {
+            ranges.borrow_mut().push((motion_type, range));
+        }
+    })
+    .unwrap();
+
+    assert_eq!("g@", keys);
+
+    let operatorfunc =
+        api::get_option_value::<String>("operatorfunc", &Default::default())
+            .unwrap();
+    assert!(operatorfunc.starts_with("v:lua.__nvim_oxi_operatorfuncs.f"));
+
+    api::feedkeys(c"g@iw", c"x", false);
+    api::feedkeys(c"w.", c"x", false);
+
+    assert_eq!(
+        &*ranges.borrow(),
+        &[
+            (
+                MotionType::Char,
+                OperatorRange {
+                    start: (Row1::FIRST, ByteCol(0)),
+                    end: (Row1::FIRST, ByteCol(2))
+                }
+            ),
+            (
+                MotionType::Char,
+                OperatorRange {
+                    start: (Row1::FIRST, ByteCol(4)),
+                    end: (Row1::FIRST, ByteCol(6))
+                }
+            ),
+        ]
+    );
+}
+
+#[nvim_oxi::test]
+fn quickfix_set_append_get() {
+    let buf = Buffer::current();
+
+    let id = api::Quickfix
+        .set([QfItem::buffer(buf.clone(), Row1::FIRST, 1)
+            .kind(QfItemKind::Error)
+            .text("foo")])
+        .unwrap();
+    assert_ne!(0, id);
+
+    let second = Row1::new(2).unwrap();
+
+    api::Quickfix
+        .append(id, [QfItem::buffer(buf.clone(), second, 3).end(second, 5)])
+        .unwrap();
+    api::Quickfix.set_title(id, "Foo").unwrap();
+    api::Quickfix.set_context(id, 42).unwrap();
+
+    let infos = api::Quickfix.get(id).unwrap();
+    assert_eq!(id, infos.id);
+    assert_eq!("Foo", infos.title);
+    assert_eq!(Some(42.into()), infos.context);
+    assert_eq!(2, infos.items.len());
+    assert_eq!(Some(QfItemKind::Error), infos.items[0].kind);
+    assert_eq!("foo", infos.items[0].text);
+    assert_eq!(Some(buf.clone()), infos.items[1].bufnr);
+    assert_eq!((Some(second), 3), (infos.items[1].lnum, infos.items[1].col));
+    assert_eq!(Some(5), infos.items[1].end_col);
+
+    api::Quickfix.replace(id, []).unwrap();
+    assert_eq!(0, api::Quickfix.get(id).unwrap().size);
+
+    let loclist = api::LocList(Window::current());
+    let loclist_id =
+        loclist.set([QfItem::buffer(buf, Row1::FIRST, 1)]).unwrap();
+    assert_eq!(1, loclist.get(loclist_id).unwrap().size);
+    assert_eq!(None, loclist.get(loclist_id).unwrap().context);
+
+    api::Quickfix.clear().unwrap();
+    assert!(api::Quickfix.get(id).is_err());
+
+    // Clearing the quickfix stack doesn't touch the location lists.
+    assert_eq!(1, loclist.get(loclist_id).unwrap().size);
+}
+
 #[nvim_oxi::test]
 fn set_get_del_mark() {
     let mut buf = api::create_buf(true, false).unwrap();
 
     let opts = SetMarkOpts::default();
 
-    let res = buf.set_mark('A', 1, 0, &opts);
+    let res = buf.set_mark('A', Row1::FIRST, ByteCol(0), &opts);
     assert_eq!(Ok(()), res);
 
     assert_eq!(
-        (1, 0, buf, "".into()),
+        Some((Row1::FIRST, ByteCol(0), buf, "".into())),
         api::get_mark('A', &Default::default()).unwrap()
     );
 
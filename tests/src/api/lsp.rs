@@ -0,0 +1,76 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use nvim_oxi::Object;
+use nvim_oxi::api::{self, Buffer, lsp::*};
+
+struct Server {
+    methods: Rc<RefCell<Vec<String>>>,
+}
+
+impl LanguageServer for Server {
+    fn on_request(
+        &mut self,
+        _: &Client,
+        method: &str,
+        _: Object,
+    ) -> Result<Object, ResponseError> {
+        self.methods.borrow_mut().push(method.to_owned());
+        match method {
+            "initialize" => Ok(nvim_oxi::Dictionary::from_iter([(
+                "capabilities",
+                nvim_oxi::Dictionary::new(),
+            )])
+            .into()),
+            "shutdown" => Ok(Object::nil()),
+            "reenter" => {
+                // Sends a notification while the server is handling this
+                // request.
+                api::command(
+                    "lua vim.lsp.get_clients({ name = 'oxi' \
+                     })[1].rpc.notify('reentered', {})",
+                )
+                .unwrap();
+                Ok(Object::nil())
+            },
+            other => Err(ResponseError::method_not_found(other)),
+        }
+    }
+
+    fn on_notification(&mut self, _: &Client, method: &str, _: Object) {
+        self.methods.borrow_mut().push(method.to_owned());
+    }
+}
+
+#[nvim_oxi::test]
+fn lsp_start_in_process_server() {
+    let methods = Rc::new(RefCell::new(Vec::new()));
+    let server = Server { methods: Rc::clone(&methods) };
+
+    let config =
+        ServerConfig::new("oxi").root_dir("/").buffer(&Buffer::current());
+    let id = start(&config, server).unwrap();
+    assert!(id > 0);
+
+    // The `initialize` request is sent as soon as the client is created.
+    assert_eq!(&["initialize"], &methods.borrow()[..]);
+}
+
+#[nvim_oxi::test]
+fn lsp_reentrant_notification() {
+    let methods = Rc::new(RefCell::new(Vec::new()));
+    let server = Server { methods: Rc::clone(&methods) };
+
+    let config =
+        ServerConfig::new("oxi").root_dir("/").buffer(&Buffer::current());
+    let id = start(&config, server).unwrap();
+
+    api::command(&format!(
+        "lua vim.lsp.get_client_by_id({id}).rpc.request('reenter', {{}}, \
+         function() end)"
+    ))
+    .unwrap();
+
+    // The notification is queued and handled after the request.
+    assert_eq!(&["initialize", "reenter", "reentered"], &methods.borrow()[..]);
+}
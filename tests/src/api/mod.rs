@@ -1,9 +1,12 @@
 mod autocmd;
 mod buffer;
 mod command;
+mod diagnostic;
 mod extmark;
 mod global;
+mod lsp;
 mod tabpage;
+mod treesitter;
 mod vimscript;
 mod win_config;
 mod window;
@@ -57,3 +57,117 @@ fn tabpage_set_get_win() {
 
     assert_eq!(tab.get_win().unwrap(), window);
 }
+
+#[nvim_oxi::test]
+fn tabpage_layout() {
+    use nvim_oxi::api::types::Layout;
+
+    let mut tab = TabPage::current();
+    let original = tab.get_win().unwrap();
+
+    nvim_oxi::api::command("vsplit | split").unwrap();
+    nvim_oxi::api::command("vertical resize 30 | resize 5").unwrap();
+
+    for mut window in tab.list_wins().unwrap() {
+        let buffer = nvim_oxi::api::create_buf(false, true).unwrap();
+        window.set_buf(&buffer).unwrap();
+    }
+
+    let layout = tab.layout().unwrap();
+
+    let Layout::Row(columns) = &layout else { panic!("{layout:?}") };
+    assert_eq!(2, columns.len());
+    assert!(matches!(&columns[0], Layout::Col(rows) if rows.len() == 2));
+    assert!(
+        matches!(&columns[1], Layout::Leaf { window, .. } if *window == original)
+    );
+    assert_eq!(3, layout.windows().count());
+
+    nvim_oxi::api::command("only").unwrap();
+
+    let restored = tab.apply_layout(&layout).unwrap();
+    let current = tab.layout().unwrap();
+    assert!(restored.windows().eq(current.windows()));
+
+    let Layout::Row(columns) = &restored else { panic!("{restored:?}") };
+    assert_eq!(2, columns.len());
+    assert!(matches!(&columns[0], Layout::Col(rows) if rows.len() == 2));
+    assert!(matches!(&columns[1], Layout::Leaf { .. }));
+
+    let leaves = |layout: &Layout| {
+        let mut leaves = Vec::new();
+        let mut stack = vec![layout.clone()];
+        while let Some(layout) = stack.pop() {
+            match layout {
+                Layout::Row(children) | Layout::Col(children) => {
+                    stack.extend(children.into_iter().rev())
+                },
+                Layout::Leaf { buffer, width, height, .. } => {
+                    leaves.push((buffer, width, height))
+                },
+            }
+        }
+        leaves
+    };
+
+    let saved = leaves(&layout);
+    assert_eq!(saved[0].1, 30);
+    assert_eq!(saved[0].2, 5);
+    assert_eq!(saved, leaves(&restored));
+    assert_eq!(saved, leaves(&current));
+}
+
+#[nvim_oxi::test]
+fn tabpage_apply_invalid_layout() {
+    use nvim_oxi::api::types::Layout;
+
+    let mut tab = TabPage::current();
+    nvim_oxi::api::command("vsplit").unwrap();
+    let layout = tab.layout().unwrap();
+
+    let res = tab
+        .apply_layout(&Layout::Col(vec![layout.clone(), Layout::Row(vec![])]));
+    assert!(res.is_err());
+    assert_eq!(layout, tab.layout().unwrap());
+
+    // Neovim merges nested splits with the same direction.
+    let res = tab.apply_layout(&Layout::Row(vec![layout.clone()]));
+    assert!(res.is_err());
+    assert_eq!(layout, tab.layout().unwrap());
+
+    let buffer = nvim_oxi::api::create_buf(false, true).unwrap();
+    let window = tab.get_win().unwrap();
+    let leaf =
+        Layout::Leaf { window, buffer: buffer.clone(), width: 10, height: 10 };
+    buffer.delete(&Default::default()).unwrap();
+
+    let res = tab.apply_layout(&Layout::Col(vec![layout.clone(), leaf]));
+    assert!(res.is_err());
+    assert_eq!(layout, tab.layout().unwrap());
+
+    nvim_oxi::api::command("only").unwrap();
+}
+
+#[nvim_oxi::test]
+fn tabpage_apply_layout_modified_buffer() {
+    use nvim_oxi::api::{self, opts::OptionOpts, types::Layout};
+
+    let mut tab = TabPage::current();
+    let layout = tab.layout().unwrap();
+
+    api::set_option_value("hidden", false, &OptionOpts::default()).unwrap();
+
+    api::command("vsplit | wincmd l | enew").unwrap();
+    let mut modified = api::Buffer::current();
+    modified.set_lines(.., true, ["foo"]).unwrap();
+
+    let restored = tab.apply_layout(&layout).unwrap();
+    assert!(matches!(restored, Layout::Leaf { .. }));
+    assert_eq!(1, tab.layout().unwrap().windows().count());
+
+    // The buffer was hidden instead of losing its changes.
+    let lines = modified.get_lines(.., true).unwrap().collect::<Vec<_>>();
+    assert_eq!(lines, ["foo"]);
+
+    api::set_option_value("hidden", true, &OptionOpts::default()).unwrap();
+}
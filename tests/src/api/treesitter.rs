@@ -0,0 +1,89 @@
+use nvim_oxi::api::{
+    Buffer,
+    treesitter::*,
+    types::{ByteCol, Row0},
+};
+
+fn lua_buffer(lines: &[&str]) -> Buffer {
+    let mut buf = Buffer::current();
+    buf.set_lines(.., true, lines.iter().copied()).unwrap();
+    buf
+}
+
+#[nvim_oxi::test]
+fn treesitter_walk_nodes() {
+    let buf = lua_buffer(&["local foo = 42", "return foo"]);
+
+    let tree = LanguageTree::get(&buf, Some("lua")).unwrap();
+    assert_eq!(Ok("lua".to_owned()), tree.lang());
+
+    let root = tree.root().unwrap();
+    assert_eq!(Ok("chunk".to_owned()), root.kind());
+    assert_eq!(Ok(None), root.parent());
+    assert_eq!(Ok("local foo = 42\nreturn foo".to_owned()), root.text(&buf));
+
+    let decl = root.named_child(0).unwrap().unwrap();
+    assert_eq!(Ok("variable_declaration".to_owned()), decl.kind());
+    assert_eq!(
+        Ok(Range::new(Row0(0), ByteCol(0), Row0(0), ByteCol(14))),
+        decl.range()
+    );
+    assert_eq!(Ok("local foo = 42".to_owned()), decl.text(&buf));
+    assert_eq!(Some(root), decl.parent().unwrap());
+
+    let node = tree
+        .named_node_for_range(Range::new(
+            Row0(0),
+            ByteCol(12),
+            Row0(0),
+            ByteCol(14),
+        ))
+        .unwrap()
+        .unwrap();
+    assert_eq!(Ok("number".to_owned()), node.kind());
+}
+
+#[nvim_oxi::test]
+fn treesitter_query_captures() {
+    let buf = lua_buffer(&["local foo = 42", "local bar = foo"]);
+    let root = LanguageTree::get(&buf, Some("lua")).unwrap().root().unwrap();
+
+    let query = Query::parse("lua", "(identifier) @ident").unwrap();
+    assert_eq!(Ok(vec!["ident".to_owned()]), query.capture_names());
+
+    let texts = query
+        .captures(&root, &buf, ..)
+        .unwrap()
+        .into_iter()
+        .map(|capture| capture.node.text(&buf).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["foo", "bar", "foo"], texts);
+
+    let captures = query.captures(&root, &buf, Row0(1)..Row0(2)).unwrap();
+    assert_eq!(2, captures.len());
+    assert_eq!("ident", captures[0].name);
+
+    assert!(Query::parse("lua", "(not_a_node) @foo").is_err());
+}
+
+#[unsafe(no_mangle)]
+unsafe extern "C" fn tree_sitter_oxi_fake() -> *const core::ffi::c_void {
+    core::ptr::null()
+}
+
+#[nvim_oxi::test]
+fn treesitter_register_language() {
+    register("lua", ["oxilua"]).unwrap();
+    assert_eq!(Ok(Some("lua".to_owned())), get_lang("oxilua"));
+
+    let lang = Language::from_path("oxi_missing", "/does/not/exist.so");
+    assert!(lang.add().is_err());
+}
+
+#[cfg(unix)]
+#[nvim_oxi::test]
+fn treesitter_language_from_symbol() {
+    let lang = Language::from_symbol("oxi", tree_sitter_oxi_fake).unwrap();
+    assert_eq!("oxi", lang.name());
+    assert!(lang.path().exists());
+}
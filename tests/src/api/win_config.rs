@@ -1,4 +1,4 @@
-use nvim_oxi::api::{self, Buffer, Window, types::*};
+use nvim_oxi::api::{self, Buffer, FloatBuilder, Window, opts::*, types::*};
 
 #[nvim_oxi::test]
 fn open_hsplit() {
@@ -163,3 +163,125 @@ fn set_config() {
 
     assert_eq!(Ok(()), win.set_config(&config));
 }
+
+#[nvim_oxi::test]
+fn float_builder_centered() {
+    let buf = api::create_buf(false, true).unwrap();
+    let win = FloatBuilder::centered(50, 50).open(&buf, false).unwrap();
+
+    let opts = OptionOpts::default();
+    let columns = api::get_option_value::<u32>("columns", &opts).unwrap();
+    let lines = api::get_option_value::<u32>("lines", &opts).unwrap();
+    let cmdheight = api::get_option_value::<u32>("cmdheight", &opts).unwrap();
+
+    let config = win.get_config().unwrap();
+    assert_eq!(config.width, Some(columns / 2));
+    assert_eq!(config.height, Some((lines - cmdheight) / 2));
+}
+
+#[nvim_oxi::test]
+fn float_builder_fit_content() {
+    let mut buf = api::create_buf(false, true).unwrap();
+    buf.set_lines(.., true, ["foo", "foo bar baz", "foo bar"]).unwrap();
+
+    let builder = FloatBuilder::at_cursor();
+    let mut win = builder.open(&buf, false).unwrap();
+
+    let config = win.get_config().unwrap();
+    assert_eq!(config.width, Some(11));
+    assert_eq!(config.height, Some(3));
+
+    buf.set_lines(.., true, ["foo"]).unwrap();
+    builder.resize(&mut win).unwrap();
+
+    let config = win.get_config().unwrap();
+    assert_eq!(config.width, Some(3));
+    assert_eq!(config.height, Some(1));
+}
+
+#[nvim_oxi::test]
+fn float_builder_fit_content_tabs() {
+    let mut buf = api::create_buf(false, true).unwrap();
+    buf.set_lines(.., true, ["\tfoo"]).unwrap();
+
+    let opts = OptionOpts::builder().buf(buf.clone()).build();
+    api::set_option_value("tabstop", 8, &opts).unwrap();
+
+    let win = FloatBuilder::at_cursor().open(&buf, false).unwrap();
+    assert_eq!(win.get_config().unwrap().width, Some(11));
+}
+
+#[nvim_oxi::test]
+fn float_builder_auto_resize() {
+    let mut buf = api::create_buf(false, true).unwrap();
+    buf.set_lines(.., true, ["foo"]).unwrap();
+
+    let win =
+        FloatBuilder::at_cursor().auto_resize(true).open(&buf, false).unwrap();
+
+    buf.set_lines(.., true, ["foo bar", "baz"]).unwrap();
+    let opts = ExecAutocmdsOpts::builder().buffer(buf.clone()).build();
+    api::exec_autocmds(["TextChanged"], &opts).unwrap();
+
+    let config = win.get_config().unwrap();
+    assert_eq!(config.width, Some(7));
+    assert_eq!(config.height, Some(2));
+}
+
+#[nvim_oxi::test]
+fn float_builder_beside_left() {
+    let buf = api::create_buf(false, true).unwrap();
+    let right =
+        FloatBuilder::bottom_right().size(10, 3).open(&buf, false).unwrap();
+
+    let mut buf = api::create_buf(false, true).unwrap();
+    buf.set_lines(.., true, ["foo bar"]).unwrap();
+    let win = FloatBuilder::beside(&right).open(&buf, false).unwrap();
+
+    let config = win.get_config().unwrap();
+    assert_eq!(config.relative, Some(WindowRelativeTo::Window(right)));
+    assert_eq!(config.row, Some(0.0));
+    assert_eq!(config.col, Some(-7.0));
+    assert_eq!(config.width, Some(7));
+}
+
+#[nvim_oxi::test]
+fn float_builder_bottom_right() {
+    let mut buf = api::create_buf(false, true).unwrap();
+    buf.set_lines(.., true, ["foo", "bar"]).unwrap();
+    let win = FloatBuilder::bottom_right().open(&buf, false).unwrap();
+
+    let opts = OptionOpts::default();
+    let columns = api::get_option_value::<u32>("columns", &opts).unwrap();
+    let lines = api::get_option_value::<u32>("lines", &opts).unwrap();
+    let cmdheight = api::get_option_value::<u32>("cmdheight", &opts).unwrap();
+
+    let config = win.get_config().unwrap();
+    assert_eq!(config.relative, Some(WindowRelativeTo::Editor));
+    assert_eq!(config.anchor, Some(WindowAnchor::SouthEast));
+    assert_eq!(config.row, Some((lines - cmdheight) as f64));
+    assert_eq!(config.col, Some(columns as f64));
+    assert_eq!(config.width, Some(3));
+    assert_eq!(config.height, Some(2));
+}
+
+#[nvim_oxi::test]
+fn float_builder_clamps_size() {
+    let buf = api::create_buf(false, true).unwrap();
+    let win = FloatBuilder::centered(50, 50)
+        .size(10_000, 10_000)
+        .border(WindowBorder::Single)
+        .open(&buf, false)
+        .unwrap();
+
+    let opts = OptionOpts::default();
+    let columns = api::get_option_value::<u32>("columns", &opts).unwrap();
+    let lines = api::get_option_value::<u32>("lines", &opts).unwrap();
+    let cmdheight = api::get_option_value::<u32>("cmdheight", &opts).unwrap();
+
+    let config = win.get_config().unwrap();
+    assert_eq!(config.width, Some(columns - 2));
+    assert_eq!(config.height, Some(lines - cmdheight - 2));
+    assert_eq!(config.row, Some(0.0));
+    assert_eq!(config.col, Some(0.0));
+}
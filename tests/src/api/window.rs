@@ -96,15 +96,15 @@ fn set_get_cursor() {
 
     let mut win = Window::current();
 
-    assert_eq!(Ok(()), win.set_cursor(1, 2));
-    assert_eq!(Ok((1, 2)), win.get_cursor());
+    assert_eq!(Ok(()), win.set_cursor(Row1::FIRST, ByteCol(2)));
+    assert_eq!(Ok((Row1::FIRST, ByteCol(2))), win.get_cursor());
 
-    assert_eq!(Ok(()), win.set_cursor(1, 42));
-    assert_eq!(Ok((1, 2)), win.get_cursor());
+    assert_eq!(Ok(()), win.set_cursor(Row1::FIRST, ByteCol(42)));
+    assert_eq!(Ok((Row1::FIRST, ByteCol(2))), win.get_cursor());
 
     buf.set_lines(.., true, [""]).unwrap();
 
-    assert_eq!(Ok((1, 0)), win.get_cursor());
+    assert_eq!(Ok((Row1::FIRST, ByteCol(0))), win.get_cursor());
 }
 
 #[nvim_oxi::test]